@@ -0,0 +1,404 @@
+use rig::client::{CompletionClient, ProviderClient};
+use rig::completion::Prompt;
+use rig::providers::gemini::Client;
+use serde_json::Value;
+
+use crate::error::{Result, ScribeError};
+use crate::utilities::require_env;
+
+/// Rough number of characters per token, used to express budgets in tokens.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// The tools a budget applies to unless set otherwise: those returning research material.
+///
+/// Tools whose result is the prompt itself (`PromptReviewer`, `InjectionAuditor`,
+/// `PromptTester`, `ExampleGenerator`) must never be cut and are deliberately not listed.
+pub const RESEARCH_TOOLS: &[&str] = &["WebSearcher"];
+
+/// What happens to a tool result that is over its [`ContextBudget`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BudgetStrategy {
+    /// Keep the parts most relevant to the tool call and drop the rest. Free and deterministic.
+    #[default]
+    Trim,
+    /// Have a cheap model summarize the result with the tool call in mind. Falls back to
+    /// [`BudgetStrategy::Trim`] if the summary cannot be produced.
+    Summarize {
+        /// The model writing the summary, e.g. `gemini-2.0-flash-lite`.
+        model: &'static str,
+    },
+}
+
+/// A size limit for the results of research tools fed back to an agent.
+///
+/// Search results can be long enough to overflow the context window. With a budget set in
+/// [`RigScribeConfig::context_budget`](crate::RigScribeConfig), results of the tools in
+/// [`ContextBudget::tools`] that are over the limit are cut down before they are added to
+/// the chat history. Results of any other tool are passed on unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::agents::budget::{BudgetStrategy, ContextBudget};
+///
+/// let budget = ContextBudget::tokens(1_000);
+/// assert_eq!(budget.max_chars, 4_000);
+/// assert_eq!(budget.strategy, BudgetStrategy::Trim);
+/// assert!(budget.applies_to("WebSearcher"));
+/// assert!(!budget.applies_to("PromptReviewer"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextBudget {
+    /// Maximum size of a single tool result, in characters.
+    pub max_chars: usize,
+    /// How over-budget results are reduced.
+    pub strategy: BudgetStrategy,
+    /// Names of the tools whose results are fitted. Defaults to [`RESEARCH_TOOLS`].
+    pub tools: Vec<String>,
+}
+
+impl ContextBudget {
+    /// A budget of `max_chars` characters, reduced with [`BudgetStrategy::Trim`].
+    pub fn chars(max_chars: usize) -> Self {
+        Self {
+            max_chars,
+            strategy: BudgetStrategy::Trim,
+            tools: RESEARCH_TOOLS.iter().map(|tool| tool.to_string()).collect(),
+        }
+    }
+
+    /// A budget of roughly `tokens` tokens (four characters each), reduced with
+    /// [`BudgetStrategy::Trim`].
+    pub fn tokens(tokens: usize) -> Self {
+        Self::chars(tokens.saturating_mul(CHARS_PER_TOKEN))
+    }
+
+    /// Reduces over-budget results by summarizing them with `model` instead of trimming.
+    pub fn summarize_with(mut self, model: &'static str) -> Self {
+        self.strategy = BudgetStrategy::Summarize { model };
+        self
+    }
+
+    /// Applies the budget to the results of `tools` instead of [`RESEARCH_TOOLS`].
+    pub fn for_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools = tools.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether results of `tool` are fitted into the budget.
+    pub fn applies_to(&self, tool: &str) -> bool {
+        self.tools.iter().any(|name| name == tool)
+    }
+
+    /// Fits the result of a tool call into the budget.
+    ///
+    /// `arguments` are the arguments of the call; they decide which parts of the result
+    /// are relevant. Results within the budget, and results of tools the budget does not
+    /// [apply to](ContextBudget::applies_to), are returned unchanged.
+    pub async fn fit(&self, tool: &str, arguments: &Value, result: String) -> String {
+        if !self.applies_to(tool) || result.chars().count() <= self.max_chars {
+            return result;
+        }
+        let original = result.chars().count();
+        let fitted = match &self.strategy {
+            BudgetStrategy::Trim => self.trim(arguments, &result),
+            BudgetStrategy::Summarize { model } => {
+                match summarize(model, tool, arguments, &result, self.max_chars).await {
+                    // The model does not always respect the length it was asked for.
+                    Ok(summary) => self.trim(arguments, &summary),
+                    Err(e) => {
+                        tracing::warn!("Summarizing {} result failed, trimming instead: {}", tool, e);
+                        self.trim(arguments, &result)
+                    }
+                }
+            }
+        };
+        tracing::info!(
+            tool,
+            original,
+            fitted = fitted.chars().count(),
+            "Tool result reduced to fit the context budget"
+        );
+        fitted
+    }
+
+    /// Keeps the most relevant parts of `result` that fit in the budget.
+    ///
+    /// JSON results keep their shape: elements of their arrays are dropped, least relevant
+    /// first. Text results are cut into paragraphs, of which the most relevant are kept in
+    /// their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::agents::budget::ContextBudget;
+    /// use serde_json::json;
+    ///
+    /// let text = "Cats sleep for most of the day.\n\n\
+    ///             Rust has a borrow checker.\n\n\
+    ///             Dogs bark at the mailman every single morning.";
+    /// let trimmed = ContextBudget::chars(90).trim(&json!({"query": "rust borrow"}), text);
+    /// assert!(trimmed.starts_with("Rust has a borrow checker."));
+    /// assert!(!trimmed.contains("Cats"));
+    /// ```
+    pub fn trim(&self, arguments: &Value, result: &str) -> String {
+        if result.chars().count() <= self.max_chars {
+            return result.to_string();
+        }
+        let terms = terms_of(arguments);
+        if let Ok(value) = serde_json::from_str::<Value>(result)
+            && let Some(trimmed) = self.trim_json(value, &terms)
+        {
+            return trimmed;
+        }
+        self.trim_text(result, &terms)
+    }
+
+    /// Drops the least relevant array elements until the serialized value fits.
+    fn trim_json(&self, mut value: Value, terms: &[String]) -> Option<String> {
+        let mut candidates: Vec<(Option<String>, usize, usize)> = Vec::new();
+        let mut collect = |key: Option<String>, items: &Vec<Value>| {
+            for (index, item) in items.iter().enumerate() {
+                candidates.push((key.clone(), index, relevance(&item.to_string(), terms)));
+            }
+        };
+        match &value {
+            Value::Array(items) => collect(None, items),
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    if let Value::Array(items) = field {
+                        collect(Some(key.clone()), items);
+                    }
+                }
+            }
+            _ => return None,
+        }
+        // Least relevant first; among equals, later elements (ranked lower by the tool) first.
+        candidates.sort_by(|a, b| a.2.cmp(&b.2).then(b.1.cmp(&a.1)));
+
+        let mut dropped: Vec<(Option<String>, usize)> = Vec::new();
+        for (key, index, _) in candidates {
+            if value.to_string().chars().count() <= self.max_chars {
+                break;
+            }
+            // Removing shifts the indices of later elements in the same array.
+            let shift = dropped
+                .iter()
+                .filter(|(k, i)| *k == key && *i < index)
+                .count();
+            let array = match (&mut value, &key) {
+                (Value::Array(items), None) => items,
+                (Value::Object(fields), Some(key)) => match fields.get_mut(key) {
+                    Some(Value::Array(items)) => items,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            array.remove(index - shift);
+            dropped.push((key, index));
+        }
+
+        let serialized = value.to_string();
+        (serialized.chars().count() <= self.max_chars).then_some(serialized)
+    }
+
+    /// Keeps the most relevant paragraphs, in their original order.
+    fn trim_text(&self, result: &str, terms: &[String]) -> String {
+        let mut paragraphs: Vec<&str> = result
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+        if paragraphs.len() == 1 {
+            paragraphs = result.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        }
+
+        let mut ranked: Vec<(usize, usize)> = paragraphs
+            .iter()
+            .enumerate()
+            .map(|(index, p)| (index, relevance(p, terms)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let note = format!(
+            "[... trimmed from {} characters to fit the context budget]",
+            result.chars().count()
+        );
+        let room = self.max_chars.saturating_sub(note.chars().count() + 2);
+        let mut kept = Vec::new();
+        let mut used = 0;
+        for (index, _) in ranked {
+            let len = paragraphs[index].chars().count() + 2;
+            if used + len <= room {
+                kept.push(index);
+                used += len;
+            }
+        }
+        kept.sort_unstable();
+
+        let mut trimmed = kept
+            .into_iter()
+            .map(|index| paragraphs[index])
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if trimmed.is_empty() {
+            // Not even the most relevant paragraph fits: cut it.
+            trimmed = result.chars().take(room).collect();
+        }
+        if self.max_chars > note.chars().count() + 2 {
+            trimmed.push_str("\n\n");
+            trimmed.push_str(&note);
+        }
+        trimmed
+    }
+}
+
+/// Asks `model` for a summary of `result` of at most `max_chars` characters.
+async fn summarize(
+    model: &str,
+    tool: &str,
+    arguments: &Value,
+    result: &str,
+    max_chars: usize,
+) -> Result<String> {
+    require_env("GEMINI_API_KEY")?;
+    let client = Client::from_env();
+    let summarizer = client
+        .agent(model)
+        .preamble(
+            "Role: Research Assistant\n\
+            Task: Condense a tool result for another agent. Keep facts, numbers, names and URLs \
+            relevant to the tool call; drop everything else.\n\
+            Output: The condensed result only, no preamble",
+        )
+        .build();
+    let request = format!(
+        "Tool: {tool}\nCall arguments: {arguments}\nMaximum length: {max_chars} characters\n\nResult:\n{result}"
+    );
+    let summary = summarizer.prompt(request).await?;
+    if summary.trim().is_empty() {
+        return Err(ScribeError::ProtocolViolation(format!(
+            "the summary of the {tool} result was empty"
+        )));
+    }
+    Ok(summary)
+}
+
+/// The lowercase words of every string in `arguments` worth matching on.
+fn terms_of(arguments: &Value) -> Vec<String> {
+    fn strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+        match value {
+            Value::String(s) => out.push(s),
+            Value::Array(items) => items.iter().for_each(|item| strings(item, out)),
+            Value::Object(fields) => fields.values().for_each(|field| strings(field, out)),
+            _ => {}
+        }
+    }
+    let mut texts = Vec::new();
+    strings(arguments, &mut texts);
+
+    let mut terms: Vec<String> = texts
+        .iter()
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect();
+    terms.sort_unstable();
+    terms.dedup();
+    terms
+}
+
+/// How many times the `terms` occur in `text`.
+fn relevance(text: &str, terms: &[String]) -> usize {
+    let text = text.to_lowercase();
+    terms.iter().map(|term| text.matches(term.as_str()).count()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_fit_leaves_small_results_alone() {
+        let budget = ContextBudget::chars(100);
+        let result = budget.fit("Tool", &json!({}), "short".to_string()).await;
+        assert_eq!(result, "short");
+    }
+
+    #[tokio::test]
+    async fn test_fit_leaves_prompt_tools_alone() {
+        let budget = ContextBudget::chars(100);
+        let artifact = json!({"system_prompt": "x".repeat(1_000), "signature": null}).to_string();
+        let result = budget.fit("PromptReviewer", &json!({}), artifact.clone()).await;
+        assert_eq!(result, artifact);
+
+        let findings = "Rust prompts need examples.\n\n".repeat(50);
+        let result = budget.fit("WebSearcher", &json!({"query": "rust"}), findings).await;
+        assert!(result.chars().count() <= 100);
+    }
+
+    #[test]
+    fn test_for_tools() {
+        let budget = ContextBudget::chars(10).for_tools(["WebSearcher", "Deconstructor"]);
+        assert!(budget.applies_to("Deconstructor"));
+        assert!(!budget.applies_to("InjectionAuditor"));
+    }
+
+    #[test]
+    fn test_trim_text_keeps_relevant_paragraphs_in_order() {
+        let text = "An introduction that is about nothing in particular at all.\n\n\
+                    Prompt engineering tips: be specific.\n\n\
+                    Weather is nice today, very nice indeed.\n\n\
+                    More prompt engineering: give examples.";
+        let budget = ContextBudget::chars(160);
+        let trimmed = budget.trim(&json!({"query": "prompt engineering"}), text);
+
+        assert!(trimmed.chars().count() <= 160);
+        let first = trimmed.find("be specific").unwrap();
+        let second = trimmed.find("give examples").unwrap();
+        assert!(first < second);
+        assert!(!trimmed.contains("Weather"));
+        assert!(trimmed.contains("trimmed from"));
+    }
+
+    #[test]
+    fn test_trim_json_drops_least_relevant_elements() {
+        let result = json!({
+            "results": [
+                {"title": "Cooking pasta", "snippet": "Boil water for a long long time."},
+                {"title": "Rust prompts", "snippet": "How to prompt for Rust code."},
+                {"title": "Gardening", "snippet": "Water the plants every single day."}
+            ]
+        })
+        .to_string();
+        let budget = ContextBudget::chars(80);
+        let trimmed = budget.trim(&json!({"query": "rust prompts"}), &result);
+
+        let value: Value = serde_json::from_str(&trimmed).unwrap();
+        let results = value["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["title"], "Rust prompts");
+    }
+
+    #[test]
+    fn test_trim_cuts_a_single_oversized_paragraph() {
+        let text = "x".repeat(1_000);
+        let trimmed = ContextBudget::chars(200).trim(&json!({}), &text);
+        assert!(trimmed.chars().count() <= 200);
+    }
+
+    #[test]
+    fn test_tokens_and_summarize() {
+        let budget = ContextBudget::tokens(10).summarize_with("gemini-2.0-flash-lite");
+        assert_eq!(budget.max_chars, 40);
+        assert_eq!(
+            budget.strategy,
+            BudgetStrategy::Summarize { model: "gemini-2.0-flash-lite" }
+        );
+    }
+}
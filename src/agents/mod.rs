@@ -1,4 +1,6 @@
+pub mod budget;
 pub mod optimizer;
+pub mod run_log;
 
 use futures::{Stream, StreamExt};
 use rig::{
@@ -12,6 +14,11 @@ use rig::{
 use std::pin::Pin;
 use thiserror::Error;
 
+use crate::agents::budget::ContextBudget;
+use crate::agents::run_log::RunLog;
+use crate::error::{Result, ScribeError};
+use crate::observer::StreamObserver;
+
 /// Represents errors that can occur during streaming communication with an agent.
 ///
 /// # Examples
@@ -36,8 +43,36 @@ pub enum StreamingError {
     Tool(#[from] ToolSetError),
 }
 
-/// A type alias for a pinned, boxed stream of text chunks or errors.
-pub type StreamingResult = Pin<Box<dyn Stream<Item = std::result::Result<Text, StreamingError>> + Send>>;
+/// A single item yielded by [`multi_turn_prompt`].
+///
+/// Reasoning is kept apart from the answer text so callers can decide what to do
+/// with it instead of accidentally concatenating it into their output.
+#[derive(Debug, Clone)]
+pub enum StreamItem {
+    /// A chunk of the assistant's visible answer.
+    Text(Text),
+    /// A chunk of the model's reasoning (thinking) trace.
+    Reasoning(String),
+    /// The model called a tool. Yielded before the tool is executed.
+    ///
+    /// Any answer text streamed before this point belongs to an intermediate turn.
+    ToolCall {
+        /// Name of the called tool.
+        name: String,
+        /// Arguments the model passed to the tool.
+        arguments: serde_json::Value,
+    },
+    /// The conversation is over. Always the last item of a successful stream.
+    Finished {
+        /// The full chat history, starting with the history passed in and including the
+        /// prompt, every assistant turn, tool call and tool result. It can be persisted
+        /// or passed back to [`multi_turn_prompt`] to continue the conversation.
+        history: Vec<Message>,
+    },
+}
+
+/// A type alias for a pinned, boxed stream of answer/reasoning chunks or errors.
+pub type StreamingResult = Pin<Box<dyn Stream<Item = std::result::Result<StreamItem, StreamingError>> + Send>>;
 
 /// Manages a multi-turn conversation with an agent, handling tool calls and streaming responses.
 ///
@@ -46,16 +81,18 @@ pub type StreamingResult = Pin<Box<dyn Stream<Item = std::result::Result<Text, S
 /// 2. Streams the response back to the caller.
 /// 3. Detects and executes tool calls.
 /// 4. Appends tool results to the chat history and continues the conversation if necessary.
+/// 5. Yields [`StreamItem::Finished`] with the final chat history once the agent stops calling tools.
 ///
 /// # Arguments
 ///
 /// * `agent` - The configured Rig agent.
 /// * `prompt` - The initial message to start or continue the conversation.
-/// * `chat_history` - A vector of previous messages to maintain context.
+/// * `chat_history` - A vector of previous messages to maintain context. It is handed back,
+///   extended with this conversation, in [`StreamItem::Finished`].
 ///
 /// # Returns
 ///
-/// A `StreamingResult` that yields text chunks as they are generated.
+/// A `StreamingResult` that yields [`StreamItem`]s as they are generated.
 ///
 /// # Examples
 ///
@@ -63,9 +100,31 @@ pub type StreamingResult = Pin<Box<dyn Stream<Item = std::result::Result<Text, S
 /// // This example is hypothetical as it requires a configured Agent which needs API keys.
 /// ```
 pub async fn multi_turn_prompt<M>(
+    agent: Agent<M>,
+    prompt: impl Into<Message> + Send,
+    chat_history: Vec<completion::Message>,
+) -> StreamingResult
+where
+    M: CompletionModel + 'static,
+    <M as CompletionModel>::StreamingResponse: std::marker::Send,
+{
+    multi_turn_prompt_with_budget(agent, prompt, chat_history, None).await
+}
+
+/// Same as [`multi_turn_prompt`], but the results of the tools `budget` applies to (by
+/// default only research tools) are fitted into it (see [`ContextBudget::fit`]) before they
+/// are added to the chat history. With `None`, tool results are passed on unchanged.
+///
+/// # Examples
+///
+/// ```no_run
+/// // This example is hypothetical as it requires a configured Agent which needs API keys.
+/// ```
+pub async fn multi_turn_prompt_with_budget<M>(
     agent: Agent<M>,
     prompt: impl Into<Message> + Send,
     mut chat_history: Vec<completion::Message>,
+    budget: Option<ContextBudget>,
 ) -> StreamingResult
 where
     M: CompletionModel + 'static,
@@ -75,9 +134,8 @@ where
 
     (Box::pin(async_stream::stream! {
         let mut current_prompt = prompt;
-        let mut did_call_tool = false;
 
-        'outer: loop {
+        loop {
             let mut stream = agent
                 .stream_completion(current_prompt.clone(), chat_history.clone())
                 .await?
@@ -86,16 +144,22 @@ where
 
             chat_history.push(current_prompt.clone());
 
+            let mut turn_text = String::new();
             let mut tool_calls = vec![];
             let mut tool_results = vec![];
 
             while let Some(content) = stream.next().await {
                 match content {
                     Ok(StreamedAssistantContent::Text(text)) => {
-                        yield Ok(Text { text: text.text });
-                        did_call_tool = false;
+                        turn_text.push_str(&text.text);
+                        yield Ok(StreamItem::Text(Text { text: text.text }));
                     },
                     Ok(StreamedAssistantContent::ToolCall(tool_call)) => {
+                        yield Ok(StreamItem::ToolCall {
+                            name: tool_call.function.name.clone(),
+                            arguments: tool_call.function.arguments.clone(),
+                        });
+
                         let tool_result =
                             agent.tool_server_handle.call_tool(&tool_call.function.name, &tool_call.function.arguments.to_string()).await
                             .map_err(|x| StreamingError::Tool(ToolSetError::ToolCallError(ToolError::ToolCallError(x.into()))))?;
@@ -107,36 +171,41 @@ where
                             "Tool executed"
                         );
 
+                        let tool_result = match &budget {
+                            Some(budget) => budget.fit(&tool_call.function.name, &tool_call.function.arguments, tool_result).await,
+                            None => tool_result,
+                        };
+
                         let tool_call_msg = AssistantContent::ToolCall(tool_call.clone());
 
                         tool_calls.push(tool_call_msg);
                         tool_results.push((tool_call.id, tool_call.call_id, tool_result));
-
-                        did_call_tool = true;
-                        // break;
                     },
                     Ok(StreamedAssistantContent::Reasoning(rig::message::Reasoning { reasoning, .. })) => {
                         if !reasoning.is_empty() {
-                            yield Ok(Text { text: reasoning.first().unwrap().to_owned() });
+                            yield Ok(StreamItem::Reasoning(reasoning.join("")));
                         }
-                        did_call_tool = false;
                     },
                     Ok(_) => {
                         // do nothing here as we don't need to accumulate token usage
                     }
                     Err(e) => {
                         yield Err(e.into());
-                        break 'outer;
+                        return;
                     }
                 }
             }
 
-            // Add (parallel) tool calls to chat history
-            if !tool_calls.is_empty() {
-                chat_history.push(Message::Assistant {
-                    id: None,
-                    content: OneOrMany::many(tool_calls).expect("Impossible EmptyListError"),
-                });
+            let did_call_tool = !tool_calls.is_empty();
+
+            // Add the assistant turn (answer text and parallel tool calls) to chat history
+            let mut assistant_content = vec![];
+            if !turn_text.is_empty() {
+                assistant_content.push(AssistantContent::text(turn_text));
+            }
+            assistant_content.extend(tool_calls);
+            if let Ok(content) = OneOrMany::many(assistant_content) {
+                chat_history.push(Message::Assistant { id: None, content });
             }
 
             // Add tool results to chat history
@@ -161,20 +230,61 @@ where
 
             }
 
+            if !did_call_tool {
+                break;
+            }
+
             // Set the current prompt to the last message in the chat history
             current_prompt = match chat_history.pop() {
                 Some(prompt) => prompt,
                 None => unreachable!("Chat history should never be empty at this point"),
             };
-
-            if !did_call_tool {
-                break;
-            }
         }
 
+        yield Ok(StreamItem::Finished { history: chat_history });
+
     })) as _
 }
 
+/// Turns a stream error into the error returned to the caller.
+///
+/// rig reports a failed tool call only as text, so a search failure recorded in `log` is
+/// returned as [`ScribeError::Search`]; anything else is a protocol violation.
+pub(crate) fn stream_error(error: StreamingError, log: Option<&RunLog>) -> ScribeError {
+    log.and_then(RunLog::search_failure)
+        .unwrap_or_else(|| ScribeError::ProtocolViolation(error.to_string()))
+}
+
+/// Drains a [`StreamingResult`], forwarding every item to `observer`, and returns the
+/// answer text of the final turn.
+///
+/// Text streamed before a tool call belongs to an intermediate turn and is dropped. Errors
+/// are mapped with [`stream_error`].
+pub(crate) async fn collect_answer(
+    mut stream: StreamingResult,
+    observer: &dyn StreamObserver,
+    source: &str,
+    log: Option<&RunLog>,
+) -> Result<String> {
+    let mut answer = String::new();
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(StreamItem::Text(text)) => {
+                observer.on_text(source, &text.text);
+                answer.push_str(&text.text);
+            }
+            Ok(StreamItem::Reasoning(chunk)) => observer.on_reasoning(source, &chunk),
+            Ok(StreamItem::ToolCall { name, arguments }) => {
+                observer.on_tool_call(source, &name, &arguments);
+                answer.clear();
+            }
+            Ok(StreamItem::Finished { .. }) => observer.on_finished(source),
+            Err(e) => return Err(stream_error(e, log)),
+        }
+    }
+    Ok(answer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,6 +295,20 @@ mod tests {
         assert!(format!("{}", err).contains("CompletionError"));
     }
 
+    #[test]
+    fn test_stream_error_returns_recorded_search_failure() {
+        let error = || StreamingError::Completion(CompletionError::ResponseError("tool failed".into()));
+        assert!(matches!(stream_error(error(), None), ScribeError::ProtocolViolation(_)));
+
+        let log = RunLog::default();
+        assert!(matches!(stream_error(error(), Some(&log)), ScribeError::ProtocolViolation(_)));
+        log.record_search_failure("rust", "quota exceeded");
+        match stream_error(error(), Some(&log)) {
+            ScribeError::Search { query, .. } => assert_eq!(query, "rust"),
+            other => panic!("Expected Search error, got {other:?}"),
+        }
+    }
+
     // TODO (UNTESTABLE): test_multi_turn_prompt
     // Requires a mock Agent<M> which is hard to construct without rig::providers::Mock (which isn't standard here).
 }
@@ -1,22 +1,43 @@
 use crate::error::{Result, ScribeError};
-use crate::types::{Artifact, Intent, MODEL};
-use crate::tools::{deconstructor::Deconstructor, prompt_reviewer::PromptReviewer, web_searcher::WebSearcher};
+use crate::types::{Artifact, ClarificationRequest, InjectionAudit, Intent, Outcome, RigScribeConfig, Specification};
+use crate::tools::{
+    deconstructor::Deconstructor,
+    example_generator::ExampleGenerator,
+    injection_auditor::InjectionAuditor,
+    prompt_reviewer::PromptReviewer,
+    prompt_tester::PromptTester,
+    submit_prompt::{SubmitPrompt, SubmitPromptArgs},
+    web_searcher::WebSearcher,
+};
 use crate::utilities::require_env;
 use rig::providers::gemini::Client;
 use futures::StreamExt;
 use rig::client::CompletionClient;
-use crate::agents::multi_turn_prompt;
+use crate::agents::{StreamItem, multi_turn_prompt_with_budget, run_log::RunLog, stream_error};
 use rig::tool::Tool;
+use std::sync::Arc;
+
+/// Source name reported to the [`StreamObserver`](crate::observer::StreamObserver) for the main agent.
+const OFFICER: &str = "PromptOfficer";
 
 /// Orchestrates the prompt optimization process.
 ///
 /// This function acts as the main entry point for the "Prompt Officer" agent.
-/// It constructs the agent with access to `Deconstructor`, `PromptReviewer`, and `WebSearcher` tools.
+/// It constructs the agent with access to `Deconstructor`, `PromptReviewer`, `WebSearcher`,
+/// `PromptTester`, `InjectionAuditor` and `ExampleGenerator` tools.
 ///
 /// # Workflow
 /// 1. **Deconstruct**: Analyze the user's intent to extract specifications.
 /// 2. **Review & Research**: Validate the request against best practices using web search.
 /// 3. **Refine**: Generate the final system prompt.
+/// 4. **Test**: Run the prompt on sample inputs and check the outputs.
+/// 5. **Harden**: Close the prompt's prompt-injection gaps.
+/// 6. **Examples**: Write worked examples, kept as [`Artifact::examples`].
+/// 7. **Submit**: Hand the prompt over through the `SubmitPrompt` tool.
+///
+/// The artifact is taken from the `SubmitPrompt` call. If the officer never calls it,
+/// the text of its final turn (the one after the last tool call) is used instead, so
+/// narration from intermediate turns never ends up in the prompt.
 ///
 /// # Arguments
 ///
@@ -40,6 +61,82 @@ use rig::tool::Tool;
 /// }
 /// ```
 pub async fn optimizer(prompt: Intent) -> Result<Artifact> {
+    optimizer_with_config(prompt, &RigScribeConfig::default()).await
+}
+
+/// Runs the optimization pipeline with an explicit [`RigScribeConfig`].
+///
+/// See [`optimizer`] for the workflow. The model's reasoning trace is never part of the
+/// returned system prompt; it is attached as [`Artifact::reasoning`] only when
+/// [`RigScribeConfig::keep_reasoning`] is set.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rigscribe::{agents::optimizer::optimizer_with_config, Intent, RigScribeConfig};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = RigScribeConfig { keep_reasoning: true, ..Default::default() };
+///     let intent = Intent::new("Optimize this").unwrap();
+///     let artifact = optimizer_with_config(intent, &config).await.unwrap();
+///     println!("{:?}", artifact.reasoning);
+/// }
+/// ```
+pub async fn optimizer_with_config(prompt: Intent, config: &RigScribeConfig) -> Result<Artifact> {
+    run_officer(prompt, None, config).await
+}
+
+/// Runs the pipeline unless the intent is too vague to optimize without guessing.
+///
+/// The `Deconstructor` analyzes the intent first. If it comes back with open questions and
+/// the intent carries no clarifications yet, [`Outcome::NeedsClarification`] is returned
+/// and nothing else runs. Otherwise the Prompt Officer continues from that specification
+/// and [`Outcome::Ready`] holds the artifact. An intent that already has clarifications is
+/// never sent back for more.
+///
+/// # Errors
+///
+/// Same as [`optimizer_with_config`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use rigscribe::{agents::optimizer::optimizer_or_clarify, Intent, Outcome, RigScribeConfig};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = RigScribeConfig::default();
+///     let intent = Intent::new("write a python fonction").unwrap();
+///     match optimizer_or_clarify(intent, &config).await.unwrap() {
+///         Outcome::Ready(artifact) => println!("{}", artifact.system_prompt),
+///         Outcome::NeedsClarification(request) => println!("{:?}", request.questions),
+///     }
+/// }
+/// ```
+pub async fn optimizer_or_clarify(prompt: Intent, config: &RigScribeConfig) -> Result<Outcome> {
+    if !prompt.clarifications.is_empty() {
+        return Ok(Outcome::Ready(run_officer(prompt, None, config).await?));
+    }
+    let spec = Deconstructor::new(config.clone()).call(prompt.clone()).await?;
+    if !spec.open_questions.is_empty() {
+        tracing::info!("Intent is under-specified, asking {} questions", spec.open_questions.len());
+        return Ok(Outcome::NeedsClarification(ClarificationRequest {
+            intent: prompt,
+            questions: spec.open_questions.clone(),
+            spec,
+        }));
+    }
+    Ok(Outcome::Ready(run_officer(prompt, Some(spec), config).await?))
+}
+
+/// Runs the Prompt Officer. With `spec`, the officer starts from that specification
+/// instead of calling the `Deconstructor` itself.
+async fn run_officer(
+    prompt: Intent,
+    spec: Option<Specification>,
+    config: &RigScribeConfig,
+) -> Result<Artifact> {
     require_env("GEMINI_API_KEY")?;
     let client = Client::new(require_env("GEMINI_API_KEY")?)?;
     let system_prompt_json = include_str!("../../data/optimizer.json");
@@ -48,62 +145,328 @@ pub async fn optimizer(prompt: Intent) -> Result<Artifact> {
     let system_prompt = artifact.system_prompt;
 
     // Log tool definitions for verbose output
-    let deconstructor_def = Deconstructor.definition("".to_string()).await;
+    let deconstructor = Deconstructor::new(config.clone())
+        // The officer may pass on only the request text; the tool falls back to the attachments.
+        .with_attachments(prompt.attachments.clone());
+    let deconstructor_def = deconstructor.definition("".to_string()).await;
     tracing::info!("Tool Definition - Deconstructor: {:?}", deconstructor_def);
 
-    let prompt_reviewer_def = PromptReviewer.definition("".to_string()).await;
+    let log = RunLog::default();
+    let prompt_reviewer = PromptReviewer::new(config.clone())
+        .with_log(log.clone())
+        // Same fallback as the Deconstructor's.
+        .with_attachments(prompt.attachments.clone());
+    let prompt_reviewer_def = prompt_reviewer.definition("".to_string()).await;
     tracing::info!("Tool Definition - PromptReviewer: {:?}", prompt_reviewer_def);
 
-    let web_searcher_def = WebSearcher.definition("".to_string()).await;
+    let web_searcher = WebSearcher::from_config(config).with_log(log.clone());
+    let web_searcher_def = web_searcher.definition("".to_string()).await;
     tracing::info!("Tool Definition - WebSearcher: {:?}", web_searcher_def);
 
+    let prompt_tester = PromptTester::new(config.clone());
+    let prompt_tester_def = prompt_tester.definition("".to_string()).await;
+    tracing::info!("Tool Definition - PromptTester: {:?}", prompt_tester_def);
+
+    let injection_auditor = InjectionAuditor::new(config.clone());
+    let injection_auditor_def = injection_auditor.definition("".to_string()).await;
+    tracing::info!("Tool Definition - InjectionAuditor: {:?}", injection_auditor_def);
+
+    let example_generator = ExampleGenerator::new(config.clone()).with_log(log.clone());
+    let example_generator_def = example_generator.definition("".to_string()).await;
+    tracing::info!("Tool Definition - ExampleGenerator: {:?}", example_generator_def);
+
+    let submit_prompt_def = SubmitPrompt.definition("".to_string()).await;
+    tracing::info!("Tool Definition - SubmitPrompt: {:?}", submit_prompt_def);
+
     let prompt_officer = client
-        .agent(MODEL)
+        .agent(config.model)
         .preamble(system_prompt.as_str())
-        .tool(Deconstructor)
-        .tool(PromptReviewer)
-        .tool(WebSearcher)
         .build();
+    let builtins: Vec<Arc<dyn rig::tool::ToolDyn>> = vec![
+        Arc::new(deconstructor),
+        Arc::new(prompt_reviewer),
+        Arc::new(web_searcher),
+        Arc::new(prompt_tester),
+        Arc::new(injection_auditor),
+        Arc::new(example_generator),
+        Arc::new(SubmitPrompt),
+    ];
+    let tools = config
+        .tools
+        .install(&prompt_officer.tool_server_handle, builtins)
+        .await?;
+    tracing::info!("Prompt Officer tools: {:?}", tools);
 
-    let input = format!(
-        "Follow this workflow to optimize the prompt:
-            1. Use the Deconstructor tool to analyze the goal and constraints of: '{}'
-            2. Use the PromptReviewer to check, you must research (using WebSearcher), and refine the draft.
-            3. Finally, provide the optimized system prompt.
-
-            Constraint: The final output must be the system prompt only, but you MUST use your tools first to arrive at that result.",
-        prompt.text
-    );
-    let mut stream =multi_turn_prompt( prompt_officer,input,Vec::new()).await;
+    let input = officer_input(&prompt, spec.as_ref(), &tools, config.language.as_deref());
+    let mut stream =multi_turn_prompt_with_budget(prompt_officer, input, Vec::new(), config.context_budget.clone()).await;
 
     tracing::info!("Starting optimization streaming...");
-    let mut optimized_prompt = String::new();
+    let mut final_turn = String::new();
+    let mut submitted: Option<String> = None;
+    let mut reasoning = String::new();
+    let mut transcript = None;
+    let observer = config.observer.as_ref();
     while let Some(res) = stream.next().await {
         match res {
-            Ok(text) => {
-                print!("{}", text.text);
-                use std::io::Write;
-                let _ = std::io::stdout().flush();
-                optimized_prompt.push_str(&text.text);
+            Ok(StreamItem::Text(text)) => {
+                observer.on_text(OFFICER, &text.text);
+                final_turn.push_str(&text.text);
+            }
+            Ok(StreamItem::ToolCall { name, arguments }) => {
+                observer.on_tool_call(OFFICER, &name, &arguments);
+                // Whatever was said before a tool call is narration, not the answer.
+                final_turn.clear();
+                if name == SubmitPrompt::NAME {
+                    match serde_json::from_value::<SubmitPromptArgs>(arguments) {
+                        Ok(args) => submitted = Some(args.system_prompt),
+                        Err(e) => tracing::warn!("Ignoring malformed SubmitPrompt call: {}", e),
+                    }
+                }
+            }
+            Ok(StreamItem::Finished { history }) => {
+                observer.on_finished(OFFICER);
+                tracing::debug!("Prompt Officer conversation finished after {} messages", history.len());
+                transcript = Some(history);
+            }
+            Ok(StreamItem::Reasoning(chunk)) => {
+                observer.on_reasoning(OFFICER, &chunk);
+                reasoning.push_str(&chunk);
             }
             Err(e) => {
                 tracing::error!("Streaming error: {}", e);
-                return Err(ScribeError::ProtocolViolation(e.to_string()));
+                return Err(stream_error(e, Some(&log)));
             }
         }
     }
-    println!();
+    let optimized_prompt = match submitted {
+        Some(prompt) => prompt,
+        None => {
+            tracing::warn!("Prompt Officer did not call SubmitPrompt; using its final answer");
+            final_turn.trim().to_string()
+        }
+    };
+    if optimized_prompt.trim().is_empty() {
+        return Err(ScribeError::ProtocolViolation(
+            "Prompt Officer finished without producing a system prompt".into(),
+        ));
+    }
     tracing::info!("Optimization complete. Final artifact length: {}", optimized_prompt.len());
-    let artifact = Artifact {
+    // What the offline checks still flag in the prompt that was actually submitted.
+    let injection_audit = tools
+        .iter()
+        .any(|tool| tool == InjectionAuditor::NAME)
+        .then(|| InjectionAudit::of(&optimized_prompt));
+    let mut artifact = Artifact {
         system_prompt: optimized_prompt,
         signed_by: "".to_string(),
+        reasoning: (config.keep_reasoning && !reasoning.is_empty()).then_some(reasoning),
+        transcript: transcript.filter(|_| config.keep_transcript),
+        sources: log.sources(),
+        review: log.last_review(),
+        score_history: log.score_history(),
+        examples: log.examples(),
+        injection_audit,
+        language: config.language.clone(),
+        translated_from: None,
+        source_digest: None,
+        signature: None,
+        variables: prompt.variables.clone(),
     };
+    for variable in &artifact.variables {
+        if !artifact.system_prompt.contains(&variable.placeholder()) {
+            tracing::warn!("The optimized prompt dropped the placeholder {}", variable.placeholder());
+        }
+    }
+    if let Some(key) = &config.signing_key {
+        artifact.sign(key);
+    }
 
     Ok(artifact)
 }
 
+/// Writes the Prompt Officer's instructions for the tools it actually has.
+fn officer_input(
+    prompt: &Intent,
+    spec: Option<&Specification>,
+    tools: &[String],
+    language: Option<&str>,
+) -> String {
+    let has = |name: &str| tools.iter().any(|tool| tool == name);
+    let builtin = [
+        Deconstructor::NAME,
+        PromptReviewer::NAME,
+        WebSearcher::NAME,
+        PromptTester::NAME,
+        InjectionAuditor::NAME,
+        ExampleGenerator::NAME,
+        SubmitPrompt::NAME,
+    ];
+
+    let mut preface = String::new();
+    let mut steps = Vec::new();
+    match spec {
+        Some(spec) => {
+            preface = format!(
+                "The Deconstructor has already analyzed: '{}'\n\n{}\n",
+                prompt.full_text(),
+                spec.to_markdown()
+            );
+            steps.push("Do not call the Deconstructor again; draft the prompt from this specification.".to_string());
+        }
+        None if has(Deconstructor::NAME) => steps.push(format!(
+            "Use the Deconstructor tool to analyze the goal and constraints of: '{}'",
+            prompt.full_text()
+        )),
+        None => steps.push(format!(
+            "Analyze the goal and constraints of: '{}'",
+            prompt.full_text()
+        )),
+    }
+    match (has(PromptReviewer::NAME), has(WebSearcher::NAME)) {
+        (true, true) => steps.push(
+            "Use the PromptReviewer to check, you must research (using WebSearcher), and refine the draft.".to_string(),
+        ),
+        (true, false) => steps.push("Use the PromptReviewer to check and refine the draft.".to_string()),
+        (false, true) => steps.push(
+            "Research best practices for this task (using WebSearcher), then refine the draft yourself.".to_string(),
+        ),
+        (false, false) => steps.push("Critically check and refine the draft yourself.".to_string()),
+    }
+    if has(PromptTester::NAME) {
+        steps.push(
+            "Use the PromptTester to run the refined prompt on sample inputs; if the outputs miss the goal, refine again.".to_string(),
+        );
+    }
+    if has(InjectionAuditor::NAME) {
+        steps.push(
+            "Use the InjectionAuditor on the refined prompt; if it reports gaps, continue with its hardened_prompt.".to_string(),
+        );
+    }
+    if has(ExampleGenerator::NAME) {
+        steps.push(
+            "Use the ExampleGenerator to write worked input/output examples for the final prompt. They are stored next to the prompt; do not paste them into it.".to_string(),
+        );
+    }
+    let custom: Vec<&str> = tools
+        .iter()
+        .map(String::as_str)
+        .filter(|tool| !builtin.contains(tool))
+        .collect();
+    if !custom.is_empty() {
+        steps.push(format!(
+            "Use {} wherever they are relevant to the request and follow what they return.",
+            custom.join(", ")
+        ));
+    }
+    if !prompt.variables.is_empty() {
+        let placeholders: Vec<String> = prompt.variables.iter().map(|v| v.placeholder()).collect();
+        steps.push(format!(
+            "Keep the template placeholders {} exactly as written; they are filled in at runtime.",
+            placeholders.join(", ")
+        ));
+    }
+    if !prompt.attachments.is_empty() {
+        steps.push(
+            "Fit the prompt to what the requester attached: it must produce the expected outputs for the sample inputs, agree with the reference documents, suit the target model and audience, and meet every hard requirement.".to_string(),
+        );
+    }
+    if let Some(language) = language {
+        steps.push(format!(
+            "Write the final system prompt in {language}. Keep placeholders such as {{{{name}}}}, tool names and code identifiers exactly as they are."
+        ));
+    }
+    if has(SubmitPrompt::NAME) {
+        steps.push("Finally, call the SubmitPrompt tool with the optimized system prompt.".to_string());
+    } else {
+        steps.push("Finally, answer with the optimized system prompt and nothing else.".to_string());
+    }
+
+    let steps = steps
+        .iter()
+        .enumerate()
+        .map(|(n, step)| format!("            {}. {}", n + 1, step))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "Follow this workflow to optimize the prompt:
+            {preface}{}
+
+            Constraint: The submitted text must be the system prompt only, but you MUST use your tools first to arrive at that result.",
+        steps.trim_start()
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn names(tools: &[&str]) -> Vec<String> {
+        tools.iter().map(|tool| tool.to_string()).collect()
+    }
+
+    #[test]
+    fn test_officer_input_default_tools() {
+        let intent = Intent::new("write a python function").unwrap();
+        let tools = names(&[
+            "Deconstructor",
+            "PromptReviewer",
+            "WebSearcher",
+            "PromptTester",
+            "InjectionAuditor",
+            "ExampleGenerator",
+            "SubmitPrompt",
+        ]);
+        let input = officer_input(&intent, None, &tools, None);
+        assert!(input.contains("1. Use the Deconstructor tool"));
+        assert!(input.contains("research (using WebSearcher)"));
+        assert!(input.contains("4. Use the InjectionAuditor"));
+        assert!(input.contains("5. Use the ExampleGenerator"));
+        assert!(input.contains("6. Finally, call the SubmitPrompt tool"));
+    }
+
+    #[test]
+    fn test_officer_input_custom_and_removed_tools() {
+        let intent = Intent::new("write a python function").unwrap();
+        let tools = names(&["Deconstructor", "PromptReviewer", "StyleGuide", "SubmitPrompt"]);
+        let input = officer_input(&intent, None, &tools, None);
+        assert!(!input.contains("WebSearcher"));
+        assert!(!input.contains("PromptTester"));
+        assert!(input.contains("Use StyleGuide wherever"));
+    }
+
+    #[test]
+    fn test_officer_input_target_language() {
+        let intent = Intent::new("write a python function").unwrap();
+        let tools = names(&["PromptReviewer", "SubmitPrompt"]);
+        let input = officer_input(&intent, None, &tools, Some("French"));
+        assert!(input.contains("3. Write the final system prompt in French. Keep placeholders such as {{name}}"));
+        assert!(input.contains("4. Finally, call the SubmitPrompt tool"));
+    }
+
+    #[test]
+    fn test_officer_input_keeps_declared_placeholders() {
+        let intent = Intent::new("answer support tickets")
+            .unwrap()
+            .with_variables(vec![crate::TemplateVariable::new("product", "The product name.")]);
+        let input = officer_input(&intent, None, &names(&["SubmitPrompt"]), None);
+        assert!(input.contains("Keep the template placeholders {{product}} exactly as written"));
+    }
+
+    #[test]
+    fn test_officer_input_fits_attachments() {
+        let intent = Intent::new("answer support tickets").unwrap();
+        let input = officer_input(&intent, None, &names(&["SubmitPrompt"]), None);
+        assert!(!input.contains("Fit the prompt"));
+
+        let intent = intent.with_attachments(crate::Attachments {
+            requirements: vec!["Never promise a refund".into()],
+            ..Default::default()
+        });
+        let input = officer_input(&intent, None, &names(&["SubmitPrompt"]), None);
+        assert!(input.contains("- Never promise a refund"));
+        assert!(input.contains("3. Fit the prompt to what the requester attached"));
+    }
+
     // TODO (UNTESTABLE): test_optimizer_flow
     // This high-level function instantiates the Client and builds an agent internally.
     // Testing it requires a full integration environment with API keys.
@@ -0,0 +1,165 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::ScribeError;
+use crate::types::{CritiqueReport, FewShotExample, SearchResult};
+
+/// Everything recorded while a single optimization runs.
+#[derive(Debug, Default)]
+struct RunRecord {
+    sources: Vec<SearchResult>,
+    reviews: Vec<CritiqueReport>,
+    score_history: Vec<u8>,
+    examples: Vec<FewShotExample>,
+    /// The (query, cause) of the first search that failed and aborted the run.
+    search_failure: Option<(String, String)>,
+}
+
+/// A handle to the record of one optimization run, shared by the tools taking part in it.
+///
+/// Tools append what they observe (for example the search results the `PromptReviewer`
+/// consulted, or its critique) and the optimizer copies it onto the final
+/// [`Artifact`](crate::Artifact).
+/// Cloning the handle shares the same record.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{agents::run_log::RunLog, SearchResult};
+///
+/// let log = RunLog::default();
+/// log.record_sources(&[SearchResult::new("Guide", "https://example.com", "Be specific.")]);
+/// assert_eq!(log.clone().sources().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunLog {
+    inner: Arc<Mutex<RunRecord>>,
+}
+
+impl RunLog {
+    fn record(&self) -> MutexGuard<'_, RunRecord> {
+        // A panic while holding the lock cannot leave the record half-written in a way
+        // that matters here, so keep going with whatever is in it.
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Records search results that were handed to an agent. Results whose URL was
+    /// already recorded are skipped.
+    pub fn record_sources(&self, results: &[SearchResult]) {
+        let mut record = self.record();
+        for result in results {
+            if !record.sources.iter().any(|source| source.url == result.url) {
+                record.sources.push(result.clone());
+            }
+        }
+    }
+
+    /// Returns the recorded sources, in the order they were first consulted.
+    pub fn sources(&self) -> Vec<SearchResult> {
+        self.record().sources.clone()
+    }
+
+    /// Records a critique produced by the `PromptReviewer`.
+    pub fn record_review(&self, review: CritiqueReport) {
+        self.record().reviews.push(review);
+    }
+
+    /// Returns the most recent critique, if the `PromptReviewer` ran.
+    pub fn last_review(&self) -> Option<CritiqueReport> {
+        self.record().reviews.last().cloned()
+    }
+
+    /// Records the scores of the revisions of the latest review.
+    pub fn record_score_history(&self, scores: Vec<u8>) {
+        self.record().score_history = scores;
+    }
+
+    /// Returns the scores recorded by the latest review.
+    pub fn score_history(&self) -> Vec<u8> {
+        self.record().score_history.clone()
+    }
+
+    /// Records generated few-shot examples, replacing earlier ones.
+    pub fn record_examples(&self, examples: Vec<FewShotExample>) {
+        self.record().examples = examples;
+    }
+
+    /// Returns the most recently generated few-shot examples.
+    pub fn examples(&self) -> Vec<FewShotExample> {
+        self.record().examples.clone()
+    }
+
+    /// Records a search failure that aborts the run. Only the first one is kept.
+    ///
+    /// The agent stream reports a failed tool call as text, so this is how the typed error
+    /// reaches the caller.
+    pub fn record_search_failure(&self, query: &str, cause: &str) {
+        let mut record = self.record();
+        if record.search_failure.is_none() {
+            record.search_failure = Some((query.to_string(), cause.to_string()));
+        }
+    }
+
+    /// Returns the recorded search failure as a [`ScribeError::Search`], if any.
+    pub fn search_failure(&self) -> Option<ScribeError> {
+        self.record()
+            .search_failure
+            .clone()
+            .map(|(query, cause)| ScribeError::Search { query, cause })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_sources_deduplicates_by_url() {
+        let log = RunLog::default();
+        log.record_sources(&[
+            SearchResult::new("A", "https://a.example", "first"),
+            SearchResult::new("B", "https://b.example", "second"),
+        ]);
+        log.record_sources(&[SearchResult::new("A again", "https://a.example", "dup")]);
+
+        let sources = log.sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].title, "A");
+    }
+
+    #[test]
+    fn test_last_review() {
+        let log = RunLog::default();
+        assert!(log.last_review().is_none());
+        for score in [40, 85] {
+            log.record_review(CritiqueReport {
+                score,
+                summary: String::new(),
+                deficits: Vec::new(),
+            });
+        }
+        assert_eq!(log.last_review().unwrap().score, 85);
+    }
+
+    #[test]
+    fn test_search_failure_keeps_the_first() {
+        let log = RunLog::default();
+        assert!(log.search_failure().is_none());
+        log.record_search_failure("rust", "quota exceeded");
+        log.record_search_failure("python", "timeout");
+        match log.search_failure() {
+            Some(ScribeError::Search { query, cause }) => {
+                assert_eq!(query, "rust");
+                assert_eq!(cause, "quota exceeded");
+            }
+            other => panic!("Expected Search error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_clones_share_the_record() {
+        let log = RunLog::default();
+        let shared = log.clone();
+        shared.record_sources(&[SearchResult::new("A", "https://a.example", "")]);
+        assert_eq!(log.sources().len(), 1);
+    }
+}
@@ -0,0 +1,369 @@
+//! Differences between two artifacts, e.g. two revisions of the same cached prompt.
+//!
+//! [`ArtifactDiff::between`] lines up the [`Sections`] of both system prompts by
+//! heading and diffs each pair line by line, so a change shows up under the section it
+//! belongs to. It also lists the metadata that changed. The result renders as Markdown for
+//! the terminal ([`ArtifactDiff::print`]) or as a unified diff ([`ArtifactDiff::to_unified`]).
+
+use similar::{Algorithm, ChangeTag, DiffTag, TextDiff, capture_diff_slices};
+use termimad::MadSkin;
+
+use crate::sections::Sections;
+use crate::types::Artifact;
+
+/// Lines of unchanged context shown around each change.
+const CONTEXT: usize = 2;
+
+/// One line of a section diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineChange {
+    /// The line is in both versions.
+    Equal(String),
+    /// The line was added.
+    Insert(String),
+    /// The line was removed.
+    Delete(String),
+}
+
+impl LineChange {
+    fn is_change(&self) -> bool {
+        !matches!(self, LineChange::Equal(_))
+    }
+}
+
+/// How a section changed between the two versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionStatus {
+    /// Same heading, same content.
+    Unchanged,
+    /// Same heading, different content.
+    Modified,
+    /// Only in the new version.
+    Added,
+    /// Only in the old version.
+    Removed,
+}
+
+/// The diff of one Markdown section of the system prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDiff {
+    /// The section's heading text; empty for the text before the first heading.
+    pub heading: String,
+    /// How the section changed.
+    pub status: SectionStatus,
+    /// The section's lines (without the heading), with what happened to each.
+    pub lines: Vec<LineChange>,
+}
+
+/// A metadata field that differs between the two versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataChange {
+    /// The field, e.g. `"language"`.
+    pub field: &'static str,
+    /// Its old value, summarized.
+    pub old: String,
+    /// Its new value, summarized.
+    pub new: String,
+}
+
+/// The difference between two artifacts.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{Artifact, diff::{ArtifactDiff, SectionStatus}};
+///
+/// let old = Artifact::new("# Persona\nYou are terse.\n# Output\nPlain text.", "v1");
+/// let new = Artifact::new("# Persona\nYou are terse.\n# Output\nJSON only.", "v2");
+/// let diff = ArtifactDiff::between(&old, &new);
+/// assert_eq!(diff.sections[1].status, SectionStatus::Modified);
+/// assert_eq!(diff.metadata[0].field, "signed_by");
+/// assert!(diff.to_unified("v1", "v2").contains("+JSON only."));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactDiff {
+    /// Every section of either version, in the new version's order with removed sections
+    /// where they used to be.
+    pub sections: Vec<SectionDiff>,
+    /// The metadata fields that changed.
+    pub metadata: Vec<MetadataChange>,
+    old_prompt: String,
+    new_prompt: String,
+}
+
+/// The (heading, body) pairs of `prompt`'s sections, leaving out a blank preamble.
+fn split_sections(prompt: &str) -> Vec<(String, String)> {
+    Sections::parse(prompt)
+        .iter()
+        .filter(|section| !section.is_preamble() || !section.body.trim().is_empty())
+        .map(|section| (section.heading.clone(), section.body.clone()))
+        .collect()
+}
+
+fn all_lines(body: &str, change: fn(String) -> LineChange) -> Vec<LineChange> {
+    body.lines().map(|line| change(line.to_string())).collect()
+}
+
+fn diff_lines(old: &str, new: &str) -> Vec<LineChange> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| {
+            let line = change.value().trim_end_matches(['\n', '\r']).to_string();
+            match change.tag() {
+                ChangeTag::Equal => LineChange::Equal(line),
+                ChangeTag::Insert => LineChange::Insert(line),
+                ChangeTag::Delete => LineChange::Delete(line),
+            }
+        })
+        .collect()
+}
+
+fn metadata_changes(old: &Artifact, new: &Artifact) -> Vec<MetadataChange> {
+    let or_none = |value: Option<&str>| value.unwrap_or("(none)").to_string();
+    let names = |artifact: &Artifact| {
+        let names: Vec<&str> = artifact.variables.iter().map(|v| v.name.as_str()).collect();
+        if names.is_empty() { "(none)".to_string() } else { names.join(", ") }
+    };
+    let fields: [(&'static str, String, String); 9] = [
+        ("signed_by", old.signed_by.clone(), new.signed_by.clone()),
+        ("language", or_none(old.language.as_deref()), or_none(new.language.as_deref())),
+        (
+            "translated_from",
+            or_none(old.translated_from.as_deref()),
+            or_none(new.translated_from.as_deref()),
+        ),
+        (
+            "source_digest",
+            or_none(old.source_digest.as_deref()),
+            or_none(new.source_digest.as_deref()),
+        ),
+        (
+            "review_score",
+            or_none(old.review.as_ref().map(|r| r.score.to_string()).as_deref()),
+            or_none(new.review.as_ref().map(|r| r.score.to_string()).as_deref()),
+        ),
+        ("variables", names(old), names(new)),
+        ("examples", old.examples.len().to_string(), new.examples.len().to_string()),
+        ("sources", old.sources.len().to_string(), new.sources.len().to_string()),
+        (
+            "signature",
+            or_none(old.signature.as_ref().map(|s| s.key_id.as_str())),
+            or_none(new.signature.as_ref().map(|s| s.key_id.as_str())),
+        ),
+    ];
+    let mut changes: Vec<MetadataChange> = fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| MetadataChange { field, old, new })
+        .collect();
+    // Same key but a new signature: the content was re-signed.
+    if let (Some(old_sig), Some(new_sig)) = (&old.signature, &new.signature)
+        && old_sig.key_id == new_sig.key_id
+        && old_sig.value != new_sig.value
+    {
+        changes.push(MetadataChange {
+            field: "signed_at",
+            old: old_sig.signed_at.to_string(),
+            new: new_sig.signed_at.to_string(),
+        });
+    }
+    changes
+}
+
+impl ArtifactDiff {
+    /// Diffs `old` against `new`.
+    pub fn between(old: &Artifact, new: &Artifact) -> Self {
+        let old_sections = split_sections(&old.system_prompt);
+        let new_sections = split_sections(&new.system_prompt);
+        let old_headings: Vec<&str> = old_sections.iter().map(|(h, _)| h.as_str()).collect();
+        let new_headings: Vec<&str> = new_sections.iter().map(|(h, _)| h.as_str()).collect();
+
+        let mut sections = Vec::new();
+        for op in capture_diff_slices(Algorithm::Myers, &old_headings, &new_headings) {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            if tag == DiffTag::Equal {
+                for (old_index, new_index) in old_range.zip(new_range) {
+                    let (heading, old_body) = &old_sections[old_index];
+                    let lines = diff_lines(old_body, &new_sections[new_index].1);
+                    let status = if lines.iter().any(LineChange::is_change) {
+                        SectionStatus::Modified
+                    } else {
+                        SectionStatus::Unchanged
+                    };
+                    sections.push(SectionDiff { heading: heading.clone(), status, lines });
+                }
+                continue;
+            }
+            for (heading, body) in &old_sections[old_range] {
+                sections.push(SectionDiff {
+                    heading: heading.clone(),
+                    status: SectionStatus::Removed,
+                    lines: all_lines(body, LineChange::Delete),
+                });
+            }
+            for (heading, body) in &new_sections[new_range] {
+                sections.push(SectionDiff {
+                    heading: heading.clone(),
+                    status: SectionStatus::Added,
+                    lines: all_lines(body, LineChange::Insert),
+                });
+            }
+        }
+
+        Self {
+            sections,
+            metadata: metadata_changes(old, new),
+            old_prompt: old.system_prompt.clone(),
+            new_prompt: new.system_prompt.clone(),
+        }
+    }
+
+    /// Whether the two artifacts have the same prompt and metadata.
+    pub fn is_empty(&self) -> bool {
+        // A heading that only changed level keeps its section unchanged, so compare the
+        // prompts themselves.
+        self.metadata.is_empty() && self.old_prompt == self.new_prompt
+    }
+
+    /// The system prompt change as a unified diff, preceded by the metadata changes as
+    /// `#` comment lines. `old_name` and `new_name` label the two sides.
+    pub fn to_unified(&self, old_name: &str, new_name: &str) -> String {
+        let mut out = String::new();
+        for change in &self.metadata {
+            out.push_str(&format!("# {}: {} -> {}\n", change.field, change.old, change.new));
+        }
+        let prompt_diff = TextDiff::from_lines(&self.old_prompt, &self.new_prompt);
+        out.push_str(
+            &prompt_diff
+                .unified_diff()
+                .context_radius(3)
+                .header(old_name, new_name)
+                .to_string(),
+        );
+        out
+    }
+
+    /// The changes as Markdown: the changed metadata as a table, then each changed section
+    /// with its changed lines and a little context.
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "**No differences.**\n".to_string();
+        }
+        let mut out = String::new();
+        if !self.metadata.is_empty() {
+            out.push_str("## Metadata\n\n|Field|Old|New|\n|-|-|-|\n");
+            for change in &self.metadata {
+                out.push_str(&format!("|{}|{}|{}|\n", change.field, change.old, change.new));
+            }
+            out.push('\n');
+        }
+        for section in &self.sections {
+            let label = match section.status {
+                SectionStatus::Unchanged => continue,
+                SectionStatus::Modified => "modified",
+                SectionStatus::Added => "added",
+                SectionStatus::Removed => "removed",
+            };
+            let heading = if section.heading.is_empty() { "(preamble)" } else { &section.heading };
+            out.push_str(&format!("## {heading} *({label})*\n\n```\n"));
+            let near_change = |index: usize| {
+                let from = index.saturating_sub(CONTEXT);
+                let to = (index + CONTEXT + 1).min(section.lines.len());
+                section.lines[from..to].iter().any(LineChange::is_change)
+            };
+            let mut skipped = false;
+            for (index, line) in section.lines.iter().enumerate() {
+                if !near_change(index) {
+                    if !skipped {
+                        out.push_str("  ...\n");
+                        skipped = true;
+                    }
+                    continue;
+                }
+                skipped = false;
+                let (marker, text) = match line {
+                    LineChange::Equal(text) => (' ', text),
+                    LineChange::Insert(text) => ('+', text),
+                    LineChange::Delete(text) => ('-', text),
+                };
+                out.push_str(&format!("{marker} {text}\n"));
+            }
+            out.push_str("```\n\n");
+        }
+        out
+    }
+
+    /// Prints [`ArtifactDiff::to_markdown`] to the terminal with `skin`.
+    pub fn print(&self, skin: &MadSkin) {
+        skin.print_text(&self.to_markdown());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::SigningKey;
+
+    const OLD: &str = "You help users.\n# Persona\nFriendly.\n# Constraints\n- Be brief.\n- No jokes.\n# Notes\nNone.\n";
+    const NEW: &str = "You help users.\n# Persona\nFriendly.\n# Constraints\n- Be brief.\n- Cite sources.\n# Output Format\nJSON.\n";
+
+    #[test]
+    fn test_split_sections_drops_blank_preamble() {
+        let headings = |prompt| -> Vec<String> {
+            split_sections(prompt).into_iter().map(|(heading, _)| heading).collect()
+        };
+        assert_eq!(headings("\n\n# A\nx\n## B\ny\n"), vec!["A", "B"]);
+        assert_eq!(headings("Intro\n# A\nx\n"), vec!["", "A"]);
+    }
+
+    #[test]
+    fn test_section_statuses() {
+        let diff = ArtifactDiff::between(&Artifact::new(OLD, ""), &Artifact::new(NEW, ""));
+        let statuses: Vec<(&str, SectionStatus)> = diff
+            .sections
+            .iter()
+            .map(|s| (s.heading.as_str(), s.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("", SectionStatus::Unchanged),
+                ("Persona", SectionStatus::Unchanged),
+                ("Constraints", SectionStatus::Modified),
+                ("Notes", SectionStatus::Removed),
+                ("Output Format", SectionStatus::Added),
+            ]
+        );
+        assert!(diff.sections[2].lines.contains(&LineChange::Insert("- Cite sources.".into())));
+        assert!(diff.sections[2].lines.contains(&LineChange::Delete("- No jokes.".into())));
+        assert!(diff.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_metadata_changes_and_identical_artifacts() {
+        let old = Artifact::new(OLD, "");
+        let mut new = old.clone();
+        assert!(ArtifactDiff::between(&old, &new).is_empty());
+
+        new.language = Some("German".into());
+        new.sign(&SigningKey::hmac("ci", b"k".to_vec()));
+        let diff = ArtifactDiff::between(&old, &new);
+        let fields: Vec<&str> = diff.metadata.iter().map(|c| c.field).collect();
+        assert_eq!(fields, vec!["signed_by", "language", "signature"]);
+        assert_eq!(diff.metadata[1].old, "(none)");
+    }
+
+    #[test]
+    fn test_renderings() {
+        let diff = ArtifactDiff::between(&Artifact::new(OLD, "v1"), &Artifact::new(NEW, "v2"));
+        let unified = diff.to_unified("a/prompt", "b/prompt");
+        assert!(unified.starts_with("# signed_by: v1 -> v2\n--- a/prompt\n+++ b/prompt\n"));
+        assert!(unified.contains("\n-- No jokes.\n") && unified.contains("\n+- Cite sources.\n"));
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("|signed_by|v1|v2|"));
+        assert!(markdown.contains("## Constraints *(modified)*"));
+        assert!(markdown.contains("+ - Cite sources."));
+        assert!(!markdown.contains("## Persona"));
+    }
+}
@@ -36,10 +36,14 @@ pub enum ScribeError {
     /// The underlying LLM provider (e.g., Gemini) failed to generate a completion.
     ///
     /// This could be due to API key issues, rate limits, or network connectivity.
+    ///
+    /// The error is boxed to keep `ScribeError` small (clippy's `result_large_err`); `?` on
+    /// a plain [`PromptError`](rig::completion::PromptError) still converts into this variant.
+    /// Code that matched `Provider(err)` against the unboxed error needs `*err` or `err.as_ref()`.
     #[error(
         "LLM provider call filed: {0}. Hint: verify API key, model name, network, and maybe rate limit."
     )]
-    Provider(#[from] rig::completion::PromptError),
+    Provider(#[from] Box<rig::completion::PromptError>),
 
     /// The LLM response did not match the expected format or protocol.
     ///
@@ -53,11 +57,38 @@ pub enum ScribeError {
     #[error("Extraction failed: {0}")]
     Extraction(#[from] rig::extractor::ExtractionError),
 
+    /// A research query could not be answered by the configured search backend.
+    ///
+    /// Whether this aborts a run is controlled by
+    /// [`RigScribeConfig::on_search_failure`](crate::RigScribeConfig).
+    #[error(
+        "Search for '{query}' failed: {cause}. Hint: check the search backend, its API key and quota."
+    )]
+    Search {
+        /// The query that was sent to the backend.
+        query: String,
+        /// What went wrong.
+        cause: String,
+    },
+
+    /// An artifact's signature is missing, was made with another key, or no longer matches
+    /// its content.
+    #[error(
+        "Signature verification failed: {0}. Hint: the artifact was edited after signing, or the key is wrong."
+    )]
+    InvalidSignature(String),
+
     /// A lower-level HTTP client error occurred.
     #[error("Client error: {0}")]
     ClientError(#[from] rig::http_client::Error),
 }
 
+impl From<rig::completion::PromptError> for ScribeError {
+    fn from(err: rig::completion::PromptError) -> Self {
+        ScribeError::Provider(Box::new(err))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +118,26 @@ mod tests {
         assert!(msg.contains("Protocol violation"));
         assert!(msg.contains("Bad JSON"));
     }
+
+    #[test]
+    fn test_prompt_error_converts_to_boxed_provider() {
+        let err: ScribeError =
+            rig::completion::PromptError::PromptCancelled { chat_history: Box::default() }.into();
+        match err {
+            ScribeError::Provider(inner) => assert!(inner.to_string().contains("PromptCancelled")),
+            other => panic!("Expected Provider, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_search_error_formatting() {
+        let err = ScribeError::Search {
+            query: "rust prompts".to_string(),
+            cause: "quota exceeded".to_string(),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("rust prompts"));
+        assert!(msg.contains("quota exceeded"));
+        assert!(msg.contains("Hint"));
+    }
 }
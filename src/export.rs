@@ -0,0 +1,422 @@
+//! Exporters from [`Artifact`] to the formats prompts are consumed in.
+//!
+//! Every format carries the artifact's metadata (signer, language, template variables,
+//! review score, signature and sources) next to the system prompt and its few-shot
+//! examples. Use [`Artifact::export`] in code or `rigscribe export` from the command line.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::error::{Result, ScribeError};
+use crate::signing::Signature;
+use crate::types::{Artifact, FewShotExample, TemplateVariable, VariableType};
+
+/// A format an [`Artifact`] can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// A Markdown document with the prompt, a metadata list and the examples.
+    Markdown,
+    /// A YAML document.
+    Yaml,
+    /// An OpenAI chat request body: system message, then the examples as user/assistant turns.
+    OpenAiChat,
+    /// An Anthropic Messages request body: `system`, then the examples as `messages`.
+    AnthropicChat,
+    /// A Rust source snippet with a `SYSTEM_PROMPT` constant.
+    Rust,
+    /// A `.prompt` file: YAML front matter followed by the prompt.
+    PromptFile,
+}
+
+impl ExportFormat {
+    /// Every format, in the order they are listed in help texts.
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Markdown,
+        ExportFormat::Yaml,
+        ExportFormat::OpenAiChat,
+        ExportFormat::AnthropicChat,
+        ExportFormat::Rust,
+        ExportFormat::PromptFile,
+    ];
+
+    /// The usual file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::OpenAiChat | ExportFormat::AnthropicChat => "json",
+            ExportFormat::Rust => "rs",
+            ExportFormat::PromptFile => "prompt",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::OpenAiChat => "openai",
+            ExportFormat::AnthropicChat => "anthropic",
+            ExportFormat::Rust => "rust",
+            ExportFormat::PromptFile => "prompt",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ScribeError;
+
+    /// Parses a format name as printed by [`Display`](fmt::Display), or its file extension.
+    fn from_str(name: &str) -> Result<Self> {
+        let name = match name.trim().to_lowercase().as_str() {
+            "yml" => "yaml".to_string(),
+            name => name.to_string(),
+        };
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.to_string() == name || format.extension() == name)
+            .ok_or_else(|| {
+                let known: Vec<String> = ExportFormat::ALL.iter().map(ToString::to_string).collect();
+                ScribeError::Validation(format!(
+                    "Unknown export format '{name}'; expected one of {}",
+                    known.join(", ")
+                ))
+            })
+    }
+}
+
+/// The metadata every export carries. Empty fields are left out.
+#[derive(Debug, Serialize)]
+struct Metadata<'a> {
+    #[serde(skip_serializing_if = "str::is_empty")]
+    signed_by: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translated_from: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    review_score: Option<u8>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    variables: &'a [TemplateVariable],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<&'a Signature>,
+}
+
+impl<'a> Metadata<'a> {
+    fn of(artifact: &'a Artifact) -> Self {
+        Self {
+            signed_by: &artifact.signed_by,
+            language: artifact.language.as_deref(),
+            translated_from: artifact.translated_from.as_deref(),
+            review_score: artifact.review.as_ref().map(|review| review.score),
+            variables: &artifact.variables,
+            sources: artifact.sources.iter().map(|source| source.url.as_str()).collect(),
+            signature: artifact.signature.as_ref(),
+        }
+    }
+}
+
+/// The document the YAML export serializes.
+#[derive(Serialize)]
+struct YamlExport<'a> {
+    system_prompt: &'a str,
+    #[serde(flatten)]
+    metadata: Metadata<'a>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    examples: &'a [FewShotExample],
+}
+
+impl Artifact {
+    /// Renders the artifact in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if the artifact cannot be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Artifact, export::ExportFormat};
+    ///
+    /// let artifact = Artifact::new("You are a helpful assistant.", "release");
+    /// let body = artifact.export(ExportFormat::OpenAiChat).unwrap();
+    /// assert!(body.contains(r#""role": "system""#));
+    ///
+    /// let rust = artifact.export("rust".parse().unwrap()).unwrap();
+    /// assert!(rust.contains("pub const SYSTEM_PROMPT: &str"));
+    /// ```
+    pub fn export(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Markdown => Ok(to_markdown(self)),
+            ExportFormat::Yaml => yaml(&YamlExport {
+                system_prompt: &self.system_prompt,
+                metadata: Metadata::of(self),
+                examples: &self.examples,
+            }),
+            ExportFormat::OpenAiChat => {
+                let mut messages = vec![json!({"role": "system", "content": self.system_prompt})];
+                messages.extend(example_turns(&self.examples));
+                pretty_json(&json!({"messages": messages, "metadata": Metadata::of(self)}))
+            }
+            ExportFormat::AnthropicChat => pretty_json(&json!({
+                "system": self.system_prompt,
+                "messages": example_turns(&self.examples),
+                "metadata": Metadata::of(self),
+            })),
+            ExportFormat::Rust => to_rust(self),
+            ExportFormat::PromptFile => to_prompt_file(self),
+        }
+    }
+}
+
+fn yaml<T: Serialize>(value: &T) -> Result<String> {
+    serde_yaml::to_string(value)
+        .map_err(|e| ScribeError::Validation(format!("Failed to serialize to YAML: {e}")))
+}
+
+fn pretty_json(value: &serde_json::Value) -> Result<String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| ScribeError::Validation(format!("Failed to serialize to JSON: {e}")))
+}
+
+/// The examples as alternating user/assistant chat messages.
+fn example_turns(examples: &[FewShotExample]) -> Vec<serde_json::Value> {
+    examples
+        .iter()
+        .flat_map(|example| {
+            [
+                json!({"role": "user", "content": example.input}),
+                json!({"role": "assistant", "content": example.output}),
+            ]
+        })
+        .collect()
+}
+
+fn to_markdown(artifact: &Artifact) -> String {
+    let metadata = Metadata::of(artifact);
+    let mut out = format!("# System Prompt\n\n{}\n", artifact.system_prompt.trim_end());
+
+    let mut lines = Vec::new();
+    if !metadata.signed_by.is_empty() {
+        lines.push(format!("- **Signed by:** {}", metadata.signed_by));
+    }
+    if let Some(language) = metadata.language {
+        lines.push(format!("- **Language:** {language}"));
+    }
+    if let Some(source) = metadata.translated_from {
+        lines.push(format!("- **Translated from:** {source}"));
+    }
+    if let Some(score) = metadata.review_score {
+        lines.push(format!("- **Review score:** {score}/100"));
+    }
+    for variable in metadata.variables {
+        let default = variable
+            .default
+            .as_ref()
+            .map(|default| format!(" (default: `{default}`)"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "- **Variable** `{}` ({:?}){default}: {}",
+            variable.placeholder(),
+            variable.kind,
+            variable.description
+        ));
+    }
+    for url in &metadata.sources {
+        lines.push(format!("- **Source:** <{url}>"));
+    }
+    if let Some(signature) = metadata.signature {
+        lines.push(format!(
+            "- **Signature:** {:?} by `{}` at {}: `{}`",
+            signature.algorithm, signature.key_id, signature.signed_at, signature.value
+        ));
+    }
+    if !lines.is_empty() {
+        out.push_str(&format!("\n## Metadata\n\n{}\n", lines.join("\n")));
+    }
+
+    if !artifact.examples.is_empty() {
+        out.push_str("\n## Examples\n");
+        for (n, example) in artifact.examples.iter().enumerate() {
+            let edge = if example.edge_case { " (edge case)" } else { "" };
+            out.push_str(&format!(
+                "\n### Example {}{edge}\n\n**Input:**\n\n{}\n\n**Output:**\n\n{}\n",
+                n + 1,
+                example.input.trim_end(),
+                example.output.trim_end()
+            ));
+        }
+    }
+    out
+}
+
+/// `text` as a Rust raw string literal with enough `#`s to hold it.
+fn raw_string(text: &str) -> String {
+    let mut hashes = 1;
+    while text.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let fence = "#".repeat(hashes);
+    format!("r{fence}\"{text}\"{fence}")
+}
+
+fn to_rust(artifact: &Artifact) -> Result<String> {
+    let metadata = yaml(&Metadata::of(artifact))?;
+    let mut out = String::from("// Generated by rigscribe; regenerate it instead of editing it.\n");
+    if metadata.trim() != "{}" {
+        for line in metadata.lines() {
+            out.push_str(&format!("// {line}\n"));
+        }
+    }
+    out.push_str(&format!(
+        "\n/// The system prompt.\npub const SYSTEM_PROMPT: &str = {};\n",
+        raw_string(&artifact.system_prompt)
+    ));
+    if !artifact.examples.is_empty() {
+        out.push_str("\n/// Few-shot examples as (user input, ideal answer) pairs.\npub const EXAMPLES: &[(&str, &str)] = &[\n");
+        for example in &artifact.examples {
+            out.push_str(&format!(
+                "    ({}, {}),\n",
+                raw_string(&example.input),
+                raw_string(&example.output)
+            ));
+        }
+        out.push_str("];\n");
+    }
+    Ok(out)
+}
+
+fn to_prompt_file(artifact: &Artifact) -> Result<String> {
+    let mut front = serde_yaml::Mapping::new();
+    if !artifact.variables.is_empty() {
+        let mut schema = serde_yaml::Mapping::new();
+        let mut defaults = serde_yaml::Mapping::new();
+        for variable in &artifact.variables {
+            let kind = match variable.kind {
+                VariableType::String => "string",
+                VariableType::Integer => "integer",
+                VariableType::Number => "number",
+                VariableType::Boolean => "boolean",
+            };
+            let optional = if variable.default.is_some() { "?" } else { "" };
+            let description = if variable.description.is_empty() {
+                kind.to_string()
+            } else {
+                format!("{kind}, {}", variable.description)
+            };
+            schema.insert(format!("{}{optional}", variable.name).into(), description.into());
+            if let Some(default) = &variable.default {
+                defaults.insert(variable.name.clone().into(), default.clone().into());
+            }
+        }
+        let mut input = serde_yaml::Mapping::new();
+        input.insert("schema".into(), schema.into());
+        if !defaults.is_empty() {
+            input.insert("default".into(), defaults.into());
+        }
+        front.insert("input".into(), input.into());
+    }
+    let metadata = serde_yaml::to_value(Metadata::of(artifact))
+        .map_err(|e| ScribeError::Validation(format!("Failed to serialize to YAML: {e}")))?;
+    if metadata.as_mapping().is_some_and(|mapping| !mapping.is_empty()) {
+        front.insert("metadata".into(), metadata);
+    }
+    if !artifact.examples.is_empty() {
+        let examples = serde_yaml::to_value(&artifact.examples)
+            .map_err(|e| ScribeError::Validation(format!("Failed to serialize to YAML: {e}")))?;
+        front.insert("examples".into(), examples);
+    }
+
+    let front = if front.is_empty() { String::new() } else { yaml(&front)? };
+    Ok(format!("---\n{front}---\n{}\n", artifact.system_prompt.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CritiqueReport;
+
+    fn artifact() -> Artifact {
+        let mut artifact = Artifact::new("Support {{product}} users. Reply in \"#Markdown\".", "release");
+        artifact.language = Some("French".into());
+        artifact.review = Some(CritiqueReport {
+            score: 91,
+            summary: String::new(),
+            deficits: Vec::new(),
+        });
+        artifact.variables = vec![
+            TemplateVariable::new("product", "The product name."),
+            TemplateVariable::new("tier", "Plan").default_value("Free"),
+        ];
+        artifact.examples.push(FewShotExample {
+            input: "Hi".into(),
+            output: "Hello!".into(),
+            edge_case: false,
+        });
+        artifact
+    }
+
+    #[test]
+    fn test_format_names_round_trip() {
+        for format in ExportFormat::ALL {
+            assert_eq!(format.to_string().parse::<ExportFormat>().unwrap(), format);
+        }
+        assert_eq!("yml".parse::<ExportFormat>().unwrap(), ExportFormat::Yaml);
+        assert!("docx".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_chat_exports() {
+        let openai: serde_json::Value =
+            serde_json::from_str(&artifact().export(ExportFormat::OpenAiChat).unwrap()).unwrap();
+        assert_eq!(openai["messages"][0]["role"], "system");
+        assert_eq!(openai["messages"][1]["content"], "Hi");
+        assert_eq!(openai["metadata"]["review_score"], 91);
+
+        let anthropic: serde_json::Value =
+            serde_json::from_str(&artifact().export(ExportFormat::AnthropicChat).unwrap()).unwrap();
+        assert!(anthropic["system"].as_str().unwrap().starts_with("Support"));
+        assert_eq!(anthropic["messages"][1]["role"], "assistant");
+        assert_eq!(anthropic["metadata"]["language"], "French");
+    }
+
+    #[test]
+    fn test_yaml_and_markdown_exports() {
+        let yaml = artifact().export(ExportFormat::Yaml).unwrap();
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed["signed_by"], "release");
+        assert_eq!(parsed["examples"][0]["output"], "Hello!");
+
+        let markdown = artifact().export(ExportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# System Prompt\n\nSupport {{product}}"));
+        assert!(markdown.contains("- **Review score:** 91/100"));
+        assert!(markdown.contains("### Example 1"));
+    }
+
+    #[test]
+    fn test_rust_export_escapes_raw_strings() {
+        let rust = artifact().export(ExportFormat::Rust).unwrap();
+        assert!(rust.contains("// signed_by: release"));
+        assert!(rust.contains(r####"pub const SYSTEM_PROMPT: &str = r##"Support {{product}} users. Reply in "#Markdown"."##;"####));
+        assert!(rust.contains(r##"(r#"Hi"#, r#"Hello!"#),"##));
+    }
+
+    #[test]
+    fn test_prompt_file_front_matter() {
+        let file = artifact().export(ExportFormat::PromptFile).unwrap();
+        let (front, body) = file.trim_start_matches("---\n").split_once("---\n").unwrap();
+        let front: serde_yaml::Value = serde_yaml::from_str(front).unwrap();
+        assert_eq!(front["input"]["schema"]["product"], "string, The product name.");
+        assert_eq!(front["input"]["schema"]["tier?"], "string, Plan");
+        assert_eq!(front["input"]["default"]["tier"], "Free");
+        assert_eq!(front["metadata"]["language"], "French");
+        assert!(body.starts_with("Support {{product}}"));
+    }
+}
@@ -21,21 +21,46 @@
 //! }
 //! ```
 
+pub mod diff;
 mod error;
+pub mod export;
 pub mod agents;
 pub mod tools;
 mod types;
+pub mod lint;
 pub mod logging;
+pub mod observer;
+pub mod search;
+pub mod sections;
+pub mod signing;
 pub mod utilities;
 
 use std::path::PathBuf;
 
 pub use error::{Result, ScribeError};
-use agents::optimizer::optimizer;
+use agents::optimizer::{optimizer, optimizer_or_clarify, optimizer_with_config};
 
-pub use types::{Artifact, Intent, ScopeId, Specification};
+pub use types::{
+    Artifact, Attachments, Clarification, ClarificationRequest, CritiqueReport, Deficit,
+    DeficitCategory, FewShotExample, InjectionAudit, InjectionCheck, InjectionGap, Intent, Outcome,
+    ReferenceDocument, ReviewLoop, RigScribeConfig, ScopeId, SearchResult, Severity, Specification,
+    TemplateVariable, VariableType,
+};
 
 use crate::utilities::{read_artifact, save_artifacts};
+use rig::tool::Tool;
+use tools::translator::{Translator, TranslatorArgs};
+
+/// Turns a language name such as `"fr-CA"` or `"Japanese"` into a file-name-safe tag.
+fn language_tag(language: &str) -> String {
+    language
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_whitespace() || c == '_' { '-' } else { c })
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect()
+}
 
 /// The main client for the RigScribe engine.
 ///
@@ -43,6 +68,8 @@ use crate::utilities::{read_artifact, save_artifacts};
 pub struct RigScribe {
     /// Directory where optimized prompts are cached to avoid re-running expensive agent chains.
     cache_dir: PathBuf,
+    /// Pipeline configuration used by [`RigScribe::optimize`] and [`RigScribe::optimize_with_cache`].
+    config: RigScribeConfig,
 }
 use tracing::info;
 
@@ -62,9 +89,58 @@ impl RigScribe {
     pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
         Self {
             cache_dir: cache_dir.into(),
+            config: RigScribeConfig::default(),
         }
     }
 
+    /// Replaces the pipeline configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{RigScribe, RigScribeConfig};
+    ///
+    /// let config = RigScribeConfig { keep_reasoning: true, ..Default::default() };
+    /// let scribe = RigScribe::new("/tmp/cache").with_config(config);
+    /// assert!(scribe.config().keep_reasoning);
+    /// ```
+    pub fn with_config(mut self, config: RigScribeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Gives the Prompt Officer an additional tool, e.g. a style-guide lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{RigScribe, tools::submit_prompt::SubmitPrompt};
+    ///
+    /// let scribe = RigScribe::new("/tmp/cache").with_tool(SubmitPrompt).without_tool("WebSearcher");
+    /// assert!(!scribe.config().tools.is_enabled("WebSearcher"));
+    /// ```
+    pub fn with_tool(mut self, tool: impl rig::tool::Tool + 'static) -> Self {
+        self.config.tools = self.config.tools.with_tool(tool);
+        self
+    }
+
+    /// Gives the Prompt Officer every tool in `toolset`.
+    pub fn with_toolset(mut self, toolset: rig::tool::ToolSet) -> Self {
+        self.config.tools = self.config.tools.with_toolset(toolset);
+        self
+    }
+
+    /// Takes the built-in tool called `name` (e.g. `"WebSearcher"`) away from the Prompt Officer.
+    pub fn without_tool(mut self, name: impl Into<String>) -> Self {
+        self.config.tools = self.config.tools.without(name);
+        self
+    }
+
+    /// Returns the pipeline configuration.
+    pub fn config(&self) -> &RigScribeConfig {
+        &self.config
+    }
+
     /// Triggers the full agentic optimization pipeline without caching.
     ///
     /// This method converts the string request into an [`Intent`] and passes it
@@ -90,13 +166,316 @@ impl RigScribe {
     /// }
     /// ```
     pub async fn optimize_agentic(request: impl Into<String>) -> Result<Artifact> {
-        let intent = Intent {
-            text: request.into(),
-        };
+        let intent = Intent::new(request)?;
         let artifact = optimizer(intent).await?;
         Ok(artifact)
     }
 
+    /// Triggers the full agentic optimization pipeline without caching, using this
+    /// instance's [`RigScribeConfig`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RigScribe::optimize_agentic`].
+    pub async fn optimize(&self, request: impl Into<String>) -> Result<Artifact> {
+        let intent = Intent::new(request)?;
+        optimizer_with_config(intent, &self.config).await
+    }
+
+    /// Like [`RigScribe::optimize`], but for a prepared [`Intent`], e.g. one that declares
+    /// [template variables](Intent::with_variables).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RigScribe::optimize_agentic`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rigscribe::{Intent, RigScribe, TemplateVariable};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let intent = Intent::new("Answer support questions about our product")
+    ///         .unwrap()
+    ///         .with_variables(vec![TemplateVariable::new("product", "The product name.")]);
+    ///     let artifact = RigScribe::new(".cache").optimize_intent(intent).await.unwrap();
+    ///     println!("{}", artifact.render([("product", "Acme")]).unwrap());
+    /// }
+    /// ```
+    pub async fn optimize_intent(&self, intent: Intent) -> Result<Artifact> {
+        optimizer_with_config(intent, &self.config).await
+    }
+
+    /// Like [`RigScribe::optimize`], but returns clarifying questions instead of a prompt
+    /// when the request is too vague to optimize without guessing.
+    ///
+    /// Answer the questions with [`RigScribe::answer`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`RigScribe::optimize_agentic`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rigscribe::{Outcome, RigScribe};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let scribe = RigScribe::new(".cache");
+    ///     let artifact = match scribe.optimize_or_clarify("write a python fonction").await.unwrap() {
+    ///         Outcome::Ready(artifact) => artifact,
+    ///         Outcome::NeedsClarification(request) => {
+    ///             let answers = vec!["Parse dates".to_string(); request.questions.len()];
+    ///             scribe.answer(request, answers).await.unwrap()
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    pub async fn optimize_or_clarify(&self, request: impl Into<String>) -> Result<Outcome> {
+        optimizer_or_clarify(Intent::new(request)?, &self.config).await
+    }
+
+    /// Merges the answers to a [`ClarificationRequest`] into its intent and optimizes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if there is not exactly one answer per question,
+    /// otherwise the same as [`RigScribe::optimize_agentic`].
+    pub async fn answer(&self, request: ClarificationRequest, answers: Vec<String>) -> Result<Artifact> {
+        let intent = request.answer(answers)?;
+        optimizer_with_config(intent, &self.config).await
+    }
+
+    /// Returns the artifact cached under `id`, if any.
+    pub async fn cached(&self, id: ScopeId) -> Option<Artifact> {
+        let path = self.cache_path(id);
+        match read_artifact(&path).await {
+            Ok(artifact) => {
+                info!("Cache HIT: {:?} loaded from disk", path);
+                Some(artifact)
+            }
+            Err(_) => {
+                info!("Cache MIS: {:?}", path);
+                None
+            }
+        }
+    }
+
+    /// Caches `artifact` under `id`. An earlier entry is kept as the next
+    /// [revision](RigScribe::revisions) of `id`, and its cached
+    /// [variants](RigScribe::variants) are removed since they translate the earlier entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Config`] if the cache directory or file cannot be written.
+    pub async fn store(&self, id: ScopeId, artifact: &Artifact) -> Result<()> {
+        let path = self.cache_path(id);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            let number = self.revisions(id).await?.last().map_or(1, |last| last + 1);
+            let archived = self.revision_path(id, number);
+            let revisions_dir = self.revisions_dir(id);
+            tokio::fs::create_dir_all(&revisions_dir).await.map_err(|e| {
+                ScribeError::Config(format!("Failed to create the revision directory {revisions_dir:?}: {e}"))
+            })?;
+            tokio::fs::rename(&path, &archived)
+                .await
+                .map_err(|e| ScribeError::Config(format!("Failed to archive {path:?} to {archived:?}: {e}")))?;
+            info!("Previous prompt archived to: {:?}", archived);
+        }
+        for tag in self.variants(id).await? {
+            let variant = self.variant_path(id, &tag)?;
+            tokio::fs::remove_file(&variant)
+                .await
+                .map_err(|e| ScribeError::Config(format!("Failed to remove stale {variant:?}: {e}")))?;
+            info!("Stale {} variant removed: {:?}", tag, variant);
+        }
+        save_artifacts(&path, artifact).await?;
+        info!("Optimize prompt cached to: {:?}", path);
+        Ok(())
+    }
+
+    /// Lists the revision numbers archived for `id`, oldest first. Revision `n` is the
+    /// artifact that the `n`th [`store`](RigScribe::store) replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Config`] if the revision directory exists but cannot be read.
+    pub async fn revisions(&self, id: ScopeId) -> Result<Vec<usize>> {
+        let mut entries = match tokio::fs::read_dir(self.revisions_dir(id)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ScribeError::Config(format!("Failed to read the revisions: {e}"))),
+        };
+        let mut numbers = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| ScribeError::Config(format!("Failed to read the revisions: {e}")))?
+        {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(number) = name.strip_suffix(".json").and_then(|n| n.parse().ok()) {
+                numbers.push(number);
+            }
+        }
+        numbers.sort();
+        Ok(numbers)
+    }
+
+    /// Returns revision `number` of the artifact cached under `id`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rigscribe::{RigScribe, ScopeId, diff::ArtifactDiff};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let scribe = RigScribe::new("./cache");
+    ///     let id = ScopeId(1);
+    ///     let last = *scribe.revisions(id).await.unwrap().last().unwrap();
+    ///     let old = scribe.revision(id, last).await.unwrap();
+    ///     let new = scribe.cached(id).await.unwrap();
+    ///     println!("{}", ArtifactDiff::between(&old, &new).to_unified("previous", "current"));
+    /// }
+    /// ```
+    pub async fn revision(&self, id: ScopeId, number: usize) -> Option<Artifact> {
+        read_artifact(self.revision_path(id, number)).await.ok()
+    }
+
+    fn cache_path(&self, id: ScopeId) -> PathBuf {
+        self.cache_dir.join(format!("{}.json", id.0))
+    }
+
+    // A subdirectory keeps revisions out of `variants` and `lint_dir`.
+    fn revisions_dir(&self, id: ScopeId) -> PathBuf {
+        self.cache_dir.join("revisions").join(id.0.to_string())
+    }
+
+    fn revision_path(&self, id: ScopeId, number: usize) -> PathBuf {
+        self.revisions_dir(id).join(format!("{number}.json"))
+    }
+
+    fn variant_path(&self, id: ScopeId, language: &str) -> Result<PathBuf> {
+        let tag = language_tag(language);
+        if tag.is_empty() {
+            return Err(ScribeError::Validation(format!("'{language}' is not a language name")));
+        }
+        Ok(self.cache_dir.join(format!("{}.{}.json", id.0, tag)))
+    }
+
+    /// Translates `artifact` into `language`, keeping its structure, constraints and
+    /// placeholders. See [`Translator`](tools::translator::Translator). The translation is
+    /// signed if [`RigScribeConfig::signing_key`] is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if the language is empty, otherwise the same as
+    /// [`RigScribe::optimize_agentic`].
+    pub async fn translate(&self, artifact: &Artifact, language: &str) -> Result<Artifact> {
+        let args = TranslatorArgs {
+            candidate: artifact.clone(),
+            language: language.to_string(),
+        };
+        let mut translated = Translator::new(self.config.clone()).call(args).await?;
+        if let Some(key) = &self.config.signing_key {
+            translated.sign(key);
+        }
+        Ok(translated)
+    }
+
+    /// Returns the `language` variant of the artifact cached under `id`, if any.
+    pub async fn cached_variant(&self, id: ScopeId, language: &str) -> Option<Artifact> {
+        let path = self.variant_path(id, language).ok()?;
+        read_artifact(&path).await.ok()
+    }
+
+    /// Caches `artifact` as the variant of scope `id` in its [`language`](Artifact::language),
+    /// next to the artifact cached under `id` itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if the artifact has no language and
+    /// [`ScribeError::Config`] if the file cannot be written.
+    pub async fn store_variant(&self, id: ScopeId, artifact: &Artifact) -> Result<()> {
+        let language = artifact.language.as_deref().ok_or_else(|| {
+            ScribeError::Validation("Only artifacts with a language can be stored as variants".into())
+        })?;
+        let path = self.variant_path(id, language)?;
+        save_artifacts(&path, artifact).await?;
+        info!("{} variant cached to: {:?}", language, path);
+        Ok(())
+    }
+
+    /// Lists the languages of the variants cached for `id`, as they appear in their file
+    /// names, in alphabetical order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Config`] if the cache directory exists but cannot be read.
+    pub async fn variants(&self, id: ScopeId) -> Result<Vec<String>> {
+        let prefix = format!("{}.", id.0);
+        let mut entries = match tokio::fs::read_dir(&self.cache_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ScribeError::Config(format!("Failed to read the cache: {e}"))),
+        };
+        let mut languages = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| ScribeError::Config(format!("Failed to read the cache: {e}")))?
+        {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(tag) = name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".json"))
+                && !tag.is_empty()
+            {
+                languages.push(tag.to_string());
+            }
+        }
+        languages.sort();
+        Ok(languages)
+    }
+
+    /// Returns the `language` variant of the artifact cached under `id`, translating and
+    /// caching it first if needed. A cached variant is only reused if its
+    /// [`source_digest`](Artifact::source_digest) matches the artifact cached under `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if nothing is cached under `id`, otherwise the
+    /// same as [`RigScribe::translate`] and [`RigScribe::store_variant`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rigscribe::{RigScribe, ScopeId};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let scribe = RigScribe::new(".cache");
+    ///     scribe.optimize_with_cache("Answer support tickets", ScopeId(7)).await.unwrap();
+    ///     for language in ["French", "German", "Japanese"] {
+    ///         scribe.translate_with_cache(ScopeId(7), language).await.unwrap();
+    ///     }
+    ///     assert_eq!(scribe.variants(ScopeId(7)).await.unwrap(), ["french", "german", "japanese"]);
+    /// }
+    /// ```
+    pub async fn translate_with_cache(&self, id: ScopeId, language: &str) -> Result<Artifact> {
+        let original = self.cached(id).await.ok_or_else(|| {
+            ScribeError::Validation(format!("Nothing is cached under scope {} to translate", id.0))
+        })?;
+        if let Some(variant) = self.cached_variant(id, language).await
+            && variant.source_digest == Some(original.digest())
+        {
+            return Ok(variant);
+        }
+        let variant = self.translate(&original, language).await?;
+        self.store_variant(id, &variant).await?;
+        Ok(variant)
+    }
+
     /// Optimizes a prompt with filesystem-based caching.
     ///
     /// If an artifact with the given [`ScopeId`] exists in the `cache_dir`, it is returned immediately.
@@ -123,18 +502,12 @@ impl RigScribe {
         request: impl Into<String>,
         id: ScopeId,
     ) -> Result<Artifact> {
-        let file_name = format!("{}.json", id.0);
-        let path = self.cache_dir.join(file_name);
-
-        if let Ok(cached_artifact) = read_artifact(&path).await {
-            info!("Cache HIT: {:?} loaded from disk", path);
+        if let Some(cached_artifact) = self.cached(id).await {
             return Ok(cached_artifact);
         }
-        info!("Cache MIS: {:?}", path);
         info!("Optimizing ...");
-        let fresh_artifact = Self::optimize_agentic(request.into()).await?;
-        save_artifacts(&path, &fresh_artifact).await?;
-        info!("Optimize prompt cached to: {:?}", path);
+        let fresh_artifact = self.optimize(request).await?;
+        self.store(id, &fresh_artifact).await?;
         Ok(fresh_artifact)
     }
 }
@@ -147,6 +520,97 @@ mod tests {
     fn test_rigscribe_new() {
         let scribe = RigScribe::new("test_dir");
         assert_eq!(scribe.cache_dir.to_str().unwrap(), "test_dir");
+        assert!(!scribe.config.keep_reasoning);
+    }
+
+    #[test]
+    fn test_rigscribe_with_config() {
+        let config = RigScribeConfig {
+            keep_reasoning: true,
+            ..Default::default()
+        };
+        let scribe = RigScribe::new("test_dir").with_config(config);
+        assert!(scribe.config().keep_reasoning);
+    }
+
+    #[test]
+    fn test_language_tag() {
+        assert_eq!(language_tag(" fr_CA "), "fr-ca");
+        assert_eq!(language_tag("Japanese"), "japanese");
+        assert_eq!(language_tag("../etc"), "etc");
+    }
+
+    #[tokio::test]
+    async fn test_store_and_list_variants() {
+        let cache_dir = std::env::temp_dir().join("rigscribe_test_variants");
+        let _ = tokio::fs::remove_dir_all(&cache_dir).await;
+        let scribe = RigScribe::new(&cache_dir);
+        let id = ScopeId(44);
+        assert!(scribe.variants(id).await.unwrap().is_empty());
+
+        let original = Artifact::new("Be brief.", "");
+        scribe.store(id, &original).await.unwrap();
+        let mut french = Artifact::new("Sois bref.", "");
+        french.language = Some("French".into());
+        french.translated_from = Some("English".into());
+        french.source_digest = Some(original.digest());
+        scribe.store_variant(id, &french).await.unwrap();
+        assert!(matches!(
+            scribe.store_variant(id, &Artifact::new("?", "")).await,
+            Err(ScribeError::Validation(_))
+        ));
+
+        assert_eq!(scribe.variants(id).await.unwrap(), vec!["french"]);
+        let cached = scribe.translate_with_cache(id, "french").await.unwrap();
+        assert_eq!(cached.system_prompt, "Sois bref.");
+        assert_eq!(cached.translated_from.as_deref(), Some("English"));
+        assert_eq!(scribe.cached(id).await.unwrap().system_prompt, "Be brief.");
+
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_removes_stale_variants() {
+        let cache_dir = std::env::temp_dir().join("rigscribe_test_stale_variants");
+        let _ = tokio::fs::remove_dir_all(&cache_dir).await;
+        let scribe = RigScribe::new(&cache_dir);
+        let id = ScopeId(45);
+
+        let original = Artifact::new("Be brief.", "");
+        scribe.store(id, &original).await.unwrap();
+        let mut french = Artifact::new("Sois bref.", "");
+        french.language = Some("French".into());
+        french.source_digest = Some(original.digest());
+        scribe.store_variant(id, &french).await.unwrap();
+        scribe.store_variant(ScopeId(46), &french).await.unwrap();
+
+        scribe.store(id, &Artifact::new("Be thorough.", "")).await.unwrap();
+        assert!(scribe.variants(id).await.unwrap().is_empty());
+        assert!(scribe.cached_variant(id, "French").await.is_none());
+        assert_eq!(scribe.variants(ScopeId(46)).await.unwrap(), vec!["french"]);
+
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_store_archives_revisions() {
+        let cache_dir = std::env::temp_dir().join("rigscribe_test_revisions");
+        let _ = tokio::fs::remove_dir_all(&cache_dir).await;
+        let scribe = RigScribe::new(&cache_dir);
+        let id = ScopeId(48);
+        assert!(scribe.revisions(id).await.unwrap().is_empty());
+
+        for prompt in ["First.", "Second.", "Third."] {
+            scribe.store(id, &Artifact::new(prompt, "")).await.unwrap();
+        }
+        assert_eq!(scribe.revisions(id).await.unwrap(), vec![1, 2]);
+        assert_eq!(scribe.revision(id, 1).await.unwrap().system_prompt, "First.");
+        assert_eq!(scribe.revision(id, 2).await.unwrap().system_prompt, "Second.");
+        assert!(scribe.revision(id, 3).await.is_none());
+        assert_eq!(scribe.cached(id).await.unwrap().system_prompt, "Third.");
+        assert!(scribe.variants(id).await.unwrap().is_empty());
+
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
     }
 
     // TODO (UNTESTABLE): optimize_agentic requires a valid GEMINI_API_KEY and network access.
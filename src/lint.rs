@@ -0,0 +1,665 @@
+//! A deterministic, LLM-free linter for system prompts.
+//!
+//! [`lint`] checks a prompt for problems that can be found without a model: unresolved
+//! placeholders, a missing output-format instruction, contradictory `MUST`/`MUST NOT`
+//! rules, vague quantifiers, duplicated sections and excessive length. It is cheap enough
+//! to run before every review, and [`lint_dir`] runs it over a cache directory, e.g. as a
+//! CI gate through `rigscribe lint`.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::error::{Result, ScribeError};
+use crate::types::{Artifact, Severity, TemplateVariable};
+use crate::utilities::read_artifact;
+
+/// Words and phrases that leave the amount or quality up to the model.
+const VAGUE_QUANTIFIERS: &[&str] = &[
+    // Phrases come first so the words inside them are not reported twice.
+    "as appropriate",
+    "as needed",
+    "if needed",
+    "if necessary",
+    "a few",
+    "some",
+    "appropriate",
+    "appropriately",
+    "various",
+    "several",
+    "many",
+    "reasonable",
+    "suitable",
+    "etc",
+];
+
+/// Phrases that count as an output-format instruction.
+const FORMAT_HINTS: &[&str] = &[
+    "output",
+    "format",
+    "respond with",
+    "respond in",
+    "response",
+    "return ",
+    "reply with",
+    "json",
+    "markdown",
+];
+
+/// Words ignored when comparing what two rules are about.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "any", "all", "for", "with", "your", "you", "that", "this", "are", "from",
+    "into", "its", "their", "them", "use", "using", "include", "including", "ever",
+];
+
+/// Which check produced a [`LintFinding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// A template placeholder such as `{{name}}`, `[INSERT X]` or `TODO` was left in.
+    UnresolvedPlaceholder,
+    /// Nothing tells the model what shape its answer should have.
+    MissingOutputFormat,
+    /// The same thing is both required and forbidden.
+    ContradictoryRules,
+    /// A word like "some" or "appropriate" leaves the decision to the model.
+    VagueQuantifier,
+    /// A heading or paragraph appears more than once.
+    DuplicateSection,
+    /// The prompt is longer than the configured limit.
+    ExcessiveLength,
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LintRule::UnresolvedPlaceholder => "unresolved-placeholder",
+            LintRule::MissingOutputFormat => "missing-output-format",
+            LintRule::ContradictoryRules => "contradictory-rules",
+            LintRule::VagueQuantifier => "vague-quantifier",
+            LintRule::DuplicateSection => "duplicate-section",
+            LintRule::ExcessiveLength => "excessive-length",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single problem found by the linter.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LintFinding {
+    /// The check that failed.
+    pub rule: LintRule,
+    /// How much it matters.
+    pub severity: Severity,
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column (in characters) of the offending text.
+    pub column: usize,
+    /// What is wrong, quoting the offending text where there is one.
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} {} [{}] {}",
+            self.line, self.column, self.severity, self.rule, self.message
+        )
+    }
+}
+
+/// The linter's settings.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::lint::Linter;
+///
+/// let linter = Linter { max_chars: 100, ..Default::default() };
+/// let findings = linter.lint(&"Respond in JSON. ".repeat(10));
+/// assert_eq!(findings.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Linter {
+    /// Prompts longer than this many characters are flagged.
+    pub max_chars: usize,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self { max_chars: 12_000 }
+    }
+}
+
+impl Linter {
+    /// Lints `prompt` and returns the findings ordered by location.
+    pub fn lint(&self, prompt: &str) -> Vec<LintFinding> {
+        self.lint_template(prompt, &[])
+    }
+
+    /// Lints an artifact's system prompt. Placeholders of its declared
+    /// [`variables`](Artifact::variables) are expected and not reported.
+    pub fn lint_artifact(&self, artifact: &Artifact) -> Vec<LintFinding> {
+        self.lint_template(&artifact.system_prompt, &artifact.variables)
+    }
+
+    /// Lints a template prompt. Placeholders of the `variables` it declares are expected
+    /// and not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{TemplateVariable, lint::Linter};
+    ///
+    /// let tier = TemplateVariable::new("user_tier", "The customer's plan.");
+    /// let prompt = "Answer in one line of JSON for a {{user_tier}} customer.";
+    /// assert!(Linter::default().lint_template(prompt, &[tier]).is_empty());
+    /// ```
+    pub fn lint_template(&self, prompt: &str, variables: &[TemplateVariable]) -> Vec<LintFinding> {
+        let declared: Vec<&str> = variables.iter().map(|v| v.name.as_str()).collect();
+        let declared = declared.as_slice();
+        let mut findings = Vec::new();
+        check_placeholders(prompt, declared, &mut findings);
+        check_output_format(prompt, &mut findings);
+        check_contradictions(prompt, &mut findings);
+        check_vague_quantifiers(prompt, &mut findings);
+        check_duplicates(prompt, &mut findings);
+        self.check_length(prompt, &mut findings);
+        findings.sort_by_key(|finding| (finding.line, finding.column));
+        findings
+    }
+
+    fn check_length(&self, prompt: &str, findings: &mut Vec<LintFinding>) {
+        let length = prompt.chars().count();
+        if length <= self.max_chars {
+            return;
+        }
+        let (line, column) = position(prompt, char_to_byte(prompt, self.max_chars));
+        findings.push(LintFinding {
+            rule: LintRule::ExcessiveLength,
+            severity: Severity::Medium,
+            line,
+            column,
+            message: format!(
+                "the prompt is {length} characters long, over the limit of {} from here on",
+                self.max_chars
+            ),
+        });
+    }
+}
+
+/// Lints `prompt` with the default [`Linter`].
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::lint::{lint, LintRule};
+///
+/// let findings = lint("You are a helpful assistant for {{company}}. Be appropriately brief.");
+/// assert!(findings.iter().any(|f| f.rule == LintRule::UnresolvedPlaceholder));
+/// assert!(findings.iter().any(|f| f.rule == LintRule::VagueQuantifier));
+/// assert!(findings.iter().any(|f| f.rule == LintRule::MissingOutputFormat));
+/// ```
+pub fn lint(prompt: &str) -> Vec<LintFinding> {
+    Linter::default().lint(prompt)
+}
+
+/// The findings for one cached artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    /// The artifact file.
+    pub path: PathBuf,
+    /// Findings for its system prompt.
+    pub findings: Vec<LintFinding>,
+}
+
+/// Lints the system prompt of every `*.json` artifact directly inside `dir`, in file name
+/// order. Subdirectories (such as the search cache) are skipped, and so are files that
+/// cannot be read as an artifact, with a warning.
+///
+/// # Errors
+///
+/// Returns [`ScribeError::Config`] if the directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rigscribe::lint::{lint_dir, Linter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     for report in lint_dir("./.prompts_perssitense_cache", &Linter::default()).await.unwrap() {
+///         println!("{}: {} findings", report.path.display(), report.findings.len());
+///     }
+/// }
+/// ```
+pub async fn lint_dir(dir: impl AsRef<Path>, linter: &Linter) -> Result<Vec<FileReport>> {
+    let dir = dir.as_ref();
+    let read_error =
+        |e: std::io::Error| ScribeError::Config(format!("Failed to read directory {:?}: {}", dir, e));
+    let mut entries = fs::read_dir(dir).await.map_err(read_error)?;
+    let mut paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(read_error)? {
+        let path = entry.path();
+        let is_file = entry.file_type().await.map_err(read_error)?.is_file();
+        if is_file && path.extension().and_then(|e| e.to_str()) == Some("json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let artifact = match read_artifact(&path).await {
+            Ok(artifact) => artifact,
+            Err(e) => {
+                tracing::warn!("Skipping {:?}, not a readable artifact: {}", path, e);
+                continue;
+            }
+        };
+        reports.push(FileReport {
+            findings: linter.lint_artifact(&artifact),
+            path,
+        });
+    }
+    Ok(reports)
+}
+
+fn check_placeholders(prompt: &str, declared: &[&str], findings: &mut Vec<LintFinding>) {
+    let mut found = |start: usize, text: &str| {
+        let (line, column) = position(prompt, start);
+        findings.push(LintFinding {
+            rule: LintRule::UnresolvedPlaceholder,
+            severity: Severity::High,
+            line,
+            column,
+            message: format!("unresolved placeholder `{text}`"),
+        });
+    };
+
+    for (start, text) in template_variables(prompt) {
+        let name = text.trim_matches(|c| c == '{' || c == '}').trim();
+        if !declared.contains(&name) {
+            found(start, text);
+        }
+    }
+
+    // `[INSERT ...]`, `<YOUR NAME>`, `[PLACEHOLDER]`, `[COMPANY_NAME]` and the like. A
+    // single all-caps word such as `[JSON]` or `[NOTE]` is a label, not a placeholder, and
+    // angle brackets are also used for XML tags, so they need one of the markers.
+    for (open, close) in [('[', ']'), ('<', '>')] {
+        let mut rest = 0;
+        while let Some(offset) = prompt[rest..].find(open) {
+            let start = rest + offset;
+            rest = start + 1;
+            let Some(len) = prompt[start..].find(close) else {
+                break;
+            };
+            let inner = &prompt[start + 1..start + len];
+            if is_placeholder(inner, open == '[') {
+                found(start, &prompt[start..=start + len]);
+                rest = start + len + 1;
+            }
+        }
+    }
+
+    for (start, word) in words(prompt) {
+        if matches!(word, "TODO" | "TBD" | "FIXME" | "XXX") {
+            found(start, word);
+        }
+    }
+}
+
+/// Whether the text between brackets reads like a placeholder left for the author to fill
+/// in. `identifiers` also accepts all-caps names joined by underscores, like `COMPANY_NAME`.
+fn is_placeholder(inner: &str, identifiers: bool) -> bool {
+    if inner.contains('\n') {
+        return false;
+    }
+    let upper = inner.trim().to_uppercase();
+    let marked = ["INSERT", "YOUR ", "YOUR_", "PLACEHOLDER", "FILL IN"]
+        .iter()
+        .any(|marker| upper.starts_with(marker));
+    let identifier = inner.contains('_')
+        && inner.split('_').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        });
+    marked || (identifiers && identifier)
+}
+
+/// Every `{{name}}` and `{name}` template variable in `prompt`, with its byte offset.
+///
+/// JSON such as `{"a": 1}` is not an identifier and is skipped.
+pub(crate) fn template_variables(prompt: &str) -> Vec<(usize, &str)> {
+    let mut variables = Vec::new();
+    let mut rest = 0;
+    while let Some(offset) = prompt[rest..].find('{') {
+        let start = rest + offset;
+        let inner_start = if prompt[start..].starts_with("{{") { start + 2 } else { start + 1 };
+        let inner_len = prompt[inner_start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == ' '))
+            .unwrap_or(prompt.len() - inner_start);
+        let inner = prompt[inner_start..inner_start + inner_len].trim();
+        let close = inner_start + inner_len;
+        let is_identifier = !inner.is_empty()
+            && !inner.contains(' ')
+            && inner.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_');
+        if is_identifier && prompt[close..].starts_with('}') {
+            let end = if inner_start == start + 2 && prompt[close..].starts_with("}}") {
+                close + 2
+            } else {
+                close + 1
+            };
+            variables.push((start, &prompt[start..end]));
+            rest = end;
+        } else {
+            rest = start + 1;
+        }
+    }
+    variables
+}
+
+fn check_output_format(prompt: &str, findings: &mut Vec<LintFinding>) {
+    let lower = prompt.to_lowercase();
+    if !FORMAT_HINTS.iter().any(|hint| lower.contains(hint)) {
+        findings.push(LintFinding {
+            rule: LintRule::MissingOutputFormat,
+            severity: Severity::Medium,
+            line: 1,
+            column: 1,
+            message: "no instruction about the output format".into(),
+        });
+    }
+}
+
+/// A `MUST`-style rule: where it starts, whether it forbids, and what it is about.
+struct Directive {
+    start: usize,
+    negative: bool,
+    subject: Vec<String>,
+    text: String,
+}
+
+fn directives(prompt: &str) -> Vec<Directive> {
+    const NEGATIVE: &[&str] = &["must not", "mustn't", "never", "do not", "don't", "shall not"];
+    const POSITIVE: &[&str] = &["must", "always", "shall"];
+
+    let mut directives = Vec::new();
+    for (start, sentence) in sentences(prompt) {
+        let lower = sentence.to_lowercase();
+        let negative = NEGATIVE.iter().filter_map(|k| find_word(&lower, k).map(|i| (i, *k))).min();
+        let positive = POSITIVE.iter().filter_map(|k| find_word(&lower, k).map(|i| (i, *k))).min();
+        let (index, keyword, is_negative) = match (negative, positive) {
+            (Some((n, kn)), Some((p, _))) if n <= p => (n, kn, true),
+            (Some((n, kn)), None) => (n, kn, true),
+            (_, Some((p, kp))) => (p, kp, false),
+            (None, None) => continue,
+        };
+        let rest = &lower[index + keyword.len()..];
+        let mut subject: Vec<String> = rest
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() >= 3 && !STOPWORDS.contains(w))
+            .map(str::to_string)
+            .collect();
+        subject.sort();
+        subject.dedup();
+        if !subject.is_empty() {
+            directives.push(Directive {
+                start,
+                negative: is_negative,
+                subject,
+                text: sentence.trim().to_string(),
+            });
+        }
+    }
+    directives
+}
+
+fn check_contradictions(prompt: &str, findings: &mut Vec<LintFinding>) {
+    let directives = directives(prompt);
+    for negative in directives.iter().filter(|d| d.negative) {
+        for positive in directives.iter().filter(|d| !d.negative) {
+            let shared = negative
+                .subject
+                .iter()
+                .filter(|w| positive.subject.contains(w))
+                .count();
+            let smaller = negative.subject.len().min(positive.subject.len());
+            if shared > 0 && shared * 5 >= smaller * 4 {
+                let later = negative.start.max(positive.start);
+                let earlier = negative.start.min(positive.start);
+                let (line, column) = position(prompt, later);
+                let (other_line, _) = position(prompt, earlier);
+                findings.push(LintFinding {
+                    rule: LintRule::ContradictoryRules,
+                    severity: Severity::High,
+                    line,
+                    column,
+                    message: format!(
+                        "\"{}\" contradicts \"{}\" (line {other_line})",
+                        if later == negative.start { &negative.text } else { &positive.text },
+                        if later == negative.start { &positive.text } else { &negative.text },
+                    ),
+                });
+            }
+        }
+    }
+}
+
+fn check_vague_quantifiers(prompt: &str, findings: &mut Vec<LintFinding>) {
+    // ASCII lowercasing keeps byte offsets valid for `prompt`.
+    let lower = prompt.to_ascii_lowercase();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for phrase in VAGUE_QUANTIFIERS {
+        let mut rest = 0;
+        while let Some(index) = find_word(&lower[rest..], phrase) {
+            let start = rest + index;
+            let end = start + phrase.len();
+            rest = end;
+            // A longer phrase (e.g. "as appropriate") already covers the word.
+            if spans.iter().any(|&(s, e)| s <= start && end <= e) {
+                continue;
+            }
+            spans.push((start, end));
+            let (line, column) = position(prompt, start);
+            findings.push(LintFinding {
+                rule: LintRule::VagueQuantifier,
+                severity: Severity::Low,
+                line,
+                column,
+                message: format!(
+                    "vague quantifier `{}`; say exactly how much or which",
+                    &prompt[start..end]
+                ),
+            });
+        }
+    }
+}
+
+fn check_duplicates(prompt: &str, findings: &mut Vec<LintFinding>) {
+    let mut headings: Vec<String> = Vec::new();
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut offset = 0;
+    for line in prompt.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+
+        let normalized = trimmed
+            .trim_start_matches('#')
+            .trim()
+            .trim_matches(|c: char| c == '*' || c == ':')
+            .to_lowercase();
+        if trimmed.starts_with('#') && !normalized.is_empty() {
+            if headings.contains(&normalized) {
+                let (line, column) = position(prompt, start);
+                findings.push(LintFinding {
+                    rule: LintRule::DuplicateSection,
+                    severity: Severity::Medium,
+                    line,
+                    column,
+                    message: format!("section `{trimmed}` appears more than once"),
+                });
+            } else {
+                headings.push(normalized);
+            }
+        } else if trimmed.chars().count() >= 40 {
+            if paragraphs.contains(&normalized) {
+                let (line, column) = position(prompt, start);
+                findings.push(LintFinding {
+                    rule: LintRule::DuplicateSection,
+                    severity: Severity::Low,
+                    line,
+                    column,
+                    message: "this line repeats an earlier one".into(),
+                });
+            } else {
+                paragraphs.push(normalized);
+            }
+        }
+    }
+}
+
+/// 1-based line and column (in characters) of byte offset `index`.
+fn position(text: &str, index: usize) -> (usize, usize) {
+    let before = &text[..index];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, text[line_start..index].chars().count() + 1)
+}
+
+fn char_to_byte(text: &str, chars: usize) -> usize {
+    text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i)
+}
+
+/// Byte offset of `needle` in `haystack` as a whole word (or phrase).
+fn find_word(haystack: &str, needle: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '\'';
+    let mut rest = 0;
+    while let Some(offset) = haystack[rest..].find(needle) {
+        let start = rest + offset;
+        let end = start + needle.len();
+        let before_ok = !haystack[..start].chars().next_back().is_some_and(is_word);
+        let after_ok = !haystack[end..].chars().next().is_some_and(is_word);
+        if before_ok && after_ok {
+            return Some(start);
+        }
+        rest = start + needle.len().max(1);
+    }
+    None
+}
+
+/// Words of `text` with their byte offsets.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// Sentences (split on `.`, `!`, `?`, `;` and newlines) of `text` with their byte offsets.
+fn sentences(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(['.', '!', '?', ';', '\n'])
+        .filter(|sentence| !sentence.trim().is_empty())
+        .map(move |sentence| {
+            let trimmed = sentence.trim_start();
+            (trimmed.as_ptr() as usize - text.as_ptr() as usize, trimmed)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(findings: &[LintFinding]) -> Vec<LintRule> {
+        findings.iter().map(|finding| finding.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_prompt_has_no_findings() {
+        let prompt = "You are a date converter.\nRespond with one ISO 8601 date per line.";
+        assert!(lint(prompt).is_empty());
+    }
+
+    #[test]
+    fn test_placeholders_with_location() {
+        let prompt = "Respond in JSON.\nGreet {{user_name}} from [INSERT COMPANY]. TODO";
+        let findings = lint(prompt);
+        assert_eq!(
+            rules(&findings),
+            vec![LintRule::UnresolvedPlaceholder; 3]
+        );
+        assert_eq!((findings[0].line, findings[0].column), (2, 7));
+        assert!(findings[1].message.contains("[INSERT COMPANY]"));
+    }
+
+    #[test]
+    fn test_bracketed_placeholders() {
+        let prompt = "Respond in JSON.\nSign as [COMPANY_NAME] for <your name here> at [YOUR_CITY].";
+        let findings = lint(prompt);
+        assert_eq!(rules(&findings), vec![LintRule::UnresolvedPlaceholder; 3]);
+        assert!(findings.iter().any(|f| f.message.contains("<your name here>")));
+    }
+
+    #[test]
+    fn test_labels_and_tags_are_not_placeholders() {
+        let prompt = "[NOTE] Answer in [JSON] inside <answer></answer> tags. See [RFC 3339].";
+        assert!(lint(prompt).is_empty());
+    }
+
+    #[test]
+    fn test_json_is_not_a_placeholder() {
+        let prompt = r#"Respond with JSON like {"name": "x"} and nothing else."#;
+        assert!(lint(prompt).is_empty());
+    }
+
+    #[test]
+    fn test_contradictory_rules() {
+        let prompt = "Respond in Markdown.\nYou MUST include code comments.\nNever include code comments.";
+        let findings = lint(prompt);
+        assert_eq!(rules(&findings), vec![LintRule::ContradictoryRules]);
+        assert_eq!(findings[0].line, 3);
+        assert_eq!(findings[0].severity, Severity::High);
+    }
+
+    #[test]
+    fn test_vague_quantifiers_and_duplicates() {
+        let prompt = "# Rules\nOutput some examples as appropriate.\n# Rules\n";
+        let findings = lint(prompt);
+        assert_eq!(
+            rules(&findings),
+            vec![
+                LintRule::VagueQuantifier,
+                LintRule::VagueQuantifier,
+                LintRule::DuplicateSection
+            ]
+        );
+        assert_eq!(findings[2].line, 3);
+    }
+
+    #[test]
+    fn test_excessive_length() {
+        let linter = Linter { max_chars: 20 };
+        let findings = linter.lint("Respond in JSON.\nThen stop here please.");
+        assert_eq!(rules(&findings), vec![LintRule::ExcessiveLength]);
+        assert_eq!((findings[0].line, findings[0].column), (2, 4));
+    }
+
+    #[tokio::test]
+    async fn test_lint_dir_skips_subdirectories_and_other_files() {
+        let dir = std::env::temp_dir().join("rigscribe_lint_dir");
+        let _ = fs::remove_dir_all(&dir).await;
+        fs::create_dir_all(dir.join("search")).await.unwrap();
+        let artifact = crate::Artifact::new("Greet {{name}}. Respond in JSON.", "");
+        crate::utilities::save_artifacts(dir.join("1.json"), &artifact).await.unwrap();
+        fs::write(dir.join("search").join("x.json"), "{}").await.unwrap();
+        fs::write(dir.join("settings.json"), r#"{"theme": "dark"}"#).await.unwrap();
+
+        let reports = lint_dir(&dir, &Linter::default()).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(rules(&reports[0].findings), vec![LintRule::UnresolvedPlaceholder]);
+        let _ = fs::remove_dir_all(dir).await;
+    }
+}
@@ -1,34 +1,257 @@
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use rigscribe::{Result, RigScribe, ScopeId, logging};
+use rigscribe::{
+    Outcome, Result, RigScribe, RigScribeConfig, ScopeId, ScribeError, Severity,
+    agents::budget::ContextBudget, diff::ArtifactDiff, export::ExportFormat, lint::{Linter, lint_dir}, logging,
+    signing::{SigningKey, VerifyingKey}, utilities::read_artifact, observer::StdoutObserver, search::SearchCache,
+};
+use tokio::io::{AsyncBufReadExt, BufReader};
 use termimad::MadSkin;
 use tracing::info;
 
 /// CLI Entry point for RigScribe.
 ///
 /// This binary provides a command-line interface to the `RigScribe` library.
-/// It initializes logging, sets up a local cache, and runs a demo optimization task.
+/// It initializes logging, sets up a local cache, and runs a demo optimization task. If the
+/// request is too vague, the clarifying questions are asked interactively on stdin.
+///
+/// `rigscribe lint [DIR] [--deny SEVERITY]` instead lints every cached artifact offline and
+/// exits with status 1 if any finding is at least `SEVERITY` (default `high`), for use as a
+/// CI gate.
+///
+/// `rigscribe verify FILE...` checks the signature of each artifact file and exits with
+/// status 1 if any is unsigned, signed with another key, or was edited after signing. The
+/// signature covers every field of the artifact except the signature itself.
+///
+/// `rigscribe export FILE [--format FORMAT] [--out PATH]` converts an artifact file to
+/// `markdown` (the default), `yaml`, `openai`, `anthropic`, `rust` or `prompt`, printing it
+/// unless `--out` is given.
+///
+/// `rigscribe diff OLD NEW [--unified]` shows how the artifact file `NEW` differs from `OLD`,
+/// section by section, and `rigscribe diff --scope ID [--unified]` compares the artifact
+/// cached under `ID` with its last revision. `--unified` prints a unified diff instead. Like
+/// `diff(1)`, it exits with status 1 if there are differences.
 ///
 /// # Environment
 ///
-/// Requires `GEMINI_API_KEY` to be set.
+/// Requires `GEMINI_API_KEY` to be set, except for `lint`, `verify`, `export` and `diff`. Artifacts are signed
+/// when `RIGSCRIBE_HMAC_SECRET` or `RIGSCRIBE_ED25519_SECRET_KEY` is set; `verify` uses
+/// `RIGSCRIBE_HMAC_SECRET` or `RIGSCRIBE_ED25519_PUBLIC_KEY`. `RIGSCRIBE_KEY_ID` names the key.
 #[tokio::main]
 async fn main() -> Result<()> {
     let _guard = logging::init_logging();
 
     // create caching path
     let cache_path = PathBuf::from("./.prompts_perssitense_cache");
-    let scribe = RigScribe::new(cache_path);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let passed = match args.first().map(String::as_str) {
+        Some("lint") => Some(lint(&args[1..], cache_path.clone()).await?),
+        Some("verify") => Some(verify(&args[1..]).await?),
+        Some("export") => Some(export(&args[1..]).await?),
+        Some("diff") => Some(diff(&args[1..], cache_path.clone()).await?),
+        _ => None,
+    };
+    if let Some(passed) = passed {
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+    // The library is silent by default; the CLI opts into live streaming to stdout.
+    let config = RigScribeConfig {
+        observer: Arc::new(StdoutObserver),
+        // Repeat runs reuse earlier research instead of spending search quota.
+        search_cache: Some(SearchCache::new(cache_path.join("search"))),
+        context_budget: Some(ContextBudget::tokens(4_000)),
+        signing_key: SigningKey::from_env()?,
+        ..Default::default()
+    };
+    let scribe = RigScribe::new(cache_path).with_config(config);
     let id = ScopeId(2011);
     // Input: The raw, often vague user intent.
     let raw_prompt = "write a python fonction";
     info!("Starting prompt optimization process for: '{}'", raw_prompt);
 
-    // Execute the multi-agent optimization pipeline.
-    let optimized_prompt = scribe.optimize_with_cache(raw_prompt, id).await?;
+    // Execute the multi-agent optimization pipeline, asking back if the request is too vague.
+    let optimized_prompt = match scribe.cached(id).await {
+        Some(artifact) => artifact,
+        None => {
+            let artifact = match scribe.optimize_or_clarify(raw_prompt).await? {
+                Outcome::Ready(artifact) => artifact,
+                Outcome::NeedsClarification(request) => {
+                    let answers = ask(&request.questions).await?;
+                    scribe.answer(request, answers).await?
+                }
+            };
+            scribe.store(id, &artifact).await?;
+            artifact
+        }
+    };
     // Render the resulting Markdown artifact to the terminal.
     let skin = MadSkin::default();
     skin.print_text(optimized_prompt.system_prompt.as_str());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Puts each question to the user on stdin and collects the answers, in order.
+async fn ask(questions: &[String]) -> Result<Vec<String>> {
+    println!("\nThe request is ambiguous. Please answer (leave blank to let RigScribe decide):");
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut answers = Vec::with_capacity(questions.len());
+    for (n, question) in questions.iter().enumerate() {
+        print!("{}. {} ", n + 1, question);
+        std::io::stdout()
+            .flush()
+            .map_err(|e| ScribeError::Config(format!("Failed to write to stdout: {e}")))?;
+        let answer = lines
+            .next_line()
+            .await
+            .map_err(|e| ScribeError::Config(format!("Failed to read answer: {e}")))?
+            .unwrap_or_default();
+        answers.push(answer);
+    }
+    Ok(answers)
+}
+/// Lints the artifacts in the directory given in `args` (or `default_dir`) and prints the
+/// findings. Returns whether no finding reached the `--deny` severity.
+async fn lint(args: &[String], default_dir: PathBuf) -> Result<bool> {
+    let mut dir = default_dir;
+    let mut deny = Severity::High;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--deny" => {
+                deny = match args.next().map(String::as_str) {
+                    Some("low") => Severity::Low,
+                    Some("medium") => Severity::Medium,
+                    Some("high") => Severity::High,
+                    Some("critical") => Severity::Critical,
+                    other => {
+                        return Err(ScribeError::Config(format!(
+                            "--deny expects low, medium, high or critical, got {other:?}"
+                        )));
+                    }
+                }
+            }
+            path => dir = PathBuf::from(path),
+        }
+    }
+
+    let mut denied = 0;
+    for report in lint_dir(&dir, &Linter::default()).await? {
+        for finding in &report.findings {
+            println!("{}:{}", report.path.display(), finding);
+            if finding.severity >= deny {
+                denied += 1;
+            }
+        }
+    }
+    println!("{denied} finding(s) at or above {deny} severity in {}", dir.display());
+    Ok(denied == 0)
+}
+
+/// Verifies the signature of each artifact in `paths` and prints the outcome. Returns
+/// whether all of them are valid.
+async fn verify(paths: &[String]) -> Result<bool> {
+    if paths.is_empty() {
+        return Err(ScribeError::Validation("verify expects at least one artifact file".into()));
+    }
+    let key = VerifyingKey::from_env()?.ok_or_else(|| {
+        ScribeError::Config(
+            "set RIGSCRIBE_HMAC_SECRET or RIGSCRIBE_ED25519_PUBLIC_KEY to verify signatures".into(),
+        )
+    })?;
+    let mut valid = true;
+    for path in paths {
+        match read_artifact(path).await.and_then(|artifact| artifact.verify(&key)) {
+            Ok(()) => println!("{path}: OK"),
+            Err(e) => {
+                println!("{path}: FAILED ({e})");
+                valid = false;
+            }
+        }
+    }
+    Ok(valid)
+}
+
+/// Exports the artifact file in `args` to the requested format. Returns `true`; failures are
+/// errors.
+async fn export(args: &[String]) -> Result<bool> {
+    let mut file = None;
+    let mut format = ExportFormat::Markdown;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let name = args.next().map(String::as_str).unwrap_or_default();
+                format = name.parse()?;
+            }
+            "--out" => out = args.next().map(PathBuf::from),
+            path => file = Some(path.to_string()),
+        }
+    }
+    let file = file.ok_or_else(|| ScribeError::Validation("export expects an artifact file".into()))?;
+
+    let exported = read_artifact(&file).await?.export(format)?;
+    match out {
+        Some(path) => {
+            tokio::fs::write(&path, exported)
+                .await
+                .map_err(|e| ScribeError::Config(format!("Failed to write {}: {e}", path.display())))?;
+            println!("{file} exported as {format} to {}", path.display());
+        }
+        None => print!("{exported}"),
+    }
+    Ok(true)
+}
+
+/// Diffs the two artifacts named in `args`, either two files or the artifact cached under
+/// `--scope` and its last revision in `cache_dir`. Returns whether they are identical.
+async fn diff(args: &[String], cache_dir: PathBuf) -> Result<bool> {
+    let mut files = Vec::new();
+    let mut scope = None;
+    let mut unified = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--unified" => unified = true,
+            "--scope" => {
+                let id = args.next().and_then(|id| id.parse().ok()).ok_or_else(|| {
+                    ScribeError::Validation("--scope expects a numeric scope id".into())
+                })?;
+                scope = Some(ScopeId(id));
+            }
+            path => files.push(path.to_string()),
+        }
+    }
+
+    let (old_name, new_name, old, new) = match (scope, files.as_slice()) {
+        (Some(id), []) => {
+            let scribe = RigScribe::new(cache_dir);
+            let missing = || ScribeError::Validation(format!("Nothing is cached under scope {}", id.0));
+            let new = scribe.cached(id).await.ok_or_else(missing)?;
+            let last = *scribe.revisions(id).await?.last().ok_or_else(|| {
+                ScribeError::Validation(format!("Scope {} has no earlier revision", id.0))
+            })?;
+            let old = scribe.revision(id, last).await.ok_or_else(missing)?;
+            (format!("{}@{last}", id.0), format!("{}", id.0), old, new)
+        }
+        (None, [old, new]) => {
+            (old.clone(), new.clone(), read_artifact(old).await?, read_artifact(new).await?)
+        }
+        _ => {
+            return Err(ScribeError::Validation(
+                "diff expects two artifact files or --scope ID".into(),
+            ));
+        }
+    };
+
+    let diff = ArtifactDiff::between(&old, &new);
+    if unified {
+        print!("{}", diff.to_unified(&old_name, &new_name));
+    } else {
+        diff.print(&MadSkin::default());
+    }
+    Ok(diff.is_empty())
+}
@@ -0,0 +1,101 @@
+//! Observing streamed agent output.
+//!
+//! Library code never writes to stdout. Everything the agents stream is routed to the
+//! [`StreamObserver`] configured in [`RigScribeConfig::observer`](crate::RigScribeConfig),
+//! which defaults to [`SilentObserver`]. Binaries that want live output opt in with
+//! [`StdoutObserver`].
+
+use std::fmt;
+use std::io::Write;
+
+/// Receives the output of the agents as it is streamed.
+///
+/// Every callback gets the `source` that produced it: `"PromptOfficer"` for the main agent,
+/// or the tool name (for example `"Deconstructor"`) for agents running inside tools.
+/// All methods default to doing nothing, so implementations only override what they need.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::observer::StreamObserver;
+/// use std::sync::Mutex;
+///
+/// #[derive(Debug, Default)]
+/// struct Collector(Mutex<String>);
+///
+/// impl StreamObserver for Collector {
+///     fn on_text(&self, _source: &str, text: &str) {
+///         self.0.lock().unwrap().push_str(text);
+///     }
+/// }
+///
+/// let collector = Collector::default();
+/// collector.on_text("PromptOfficer", "Hello");
+/// assert_eq!(*collector.0.lock().unwrap(), "Hello");
+/// ```
+pub trait StreamObserver: Send + Sync + fmt::Debug {
+    /// A chunk of answer text.
+    fn on_text(&self, _source: &str, _text: &str) {}
+
+    /// A chunk of the model's reasoning trace.
+    fn on_reasoning(&self, _source: &str, _text: &str) {}
+
+    /// The agent called a tool.
+    fn on_tool_call(&self, _source: &str, _tool: &str, _arguments: &serde_json::Value) {}
+
+    /// The agent's stream ended.
+    fn on_finished(&self, _source: &str) {}
+}
+
+/// An observer that discards everything. This is the library default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SilentObserver;
+
+impl StreamObserver for SilentObserver {}
+
+/// An observer that prints answer text to stdout as it arrives.
+///
+/// Reasoning and tool calls are not printed; they are available through `tracing`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdoutObserver;
+
+impl StreamObserver for StdoutObserver {
+    fn on_text(&self, _source: &str, text: &str) {
+        print!("{}", text);
+        let _ = std::io::stdout().flush();
+    }
+
+    fn on_finished(&self, _source: &str) {
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl StreamObserver for Recorder {
+        fn on_tool_call(&self, source: &str, tool: &str, _arguments: &serde_json::Value) {
+            self.0.lock().unwrap().push(format!("{source}->{tool}"));
+        }
+    }
+
+    #[test]
+    fn test_default_methods_are_noops() {
+        let observer = SilentObserver;
+        observer.on_text("PromptOfficer", "ignored");
+        observer.on_finished("PromptOfficer");
+    }
+
+    #[test]
+    fn test_custom_observer_receives_tool_calls() {
+        let recorder = Recorder::default();
+        recorder.on_text("PromptOfficer", "ignored");
+        recorder.on_tool_call("PromptOfficer", "Deconstructor", &serde_json::json!({}));
+        assert_eq!(*recorder.0.lock().unwrap(), vec!["PromptOfficer->Deconstructor"]);
+    }
+}
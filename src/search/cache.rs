@@ -0,0 +1,254 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::error::Result;
+use crate::search::SearchProvider;
+use crate::types::SearchResult;
+use crate::utilities::unix_now;
+
+/// Where search results are cached and for how long they stay fresh.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::search::SearchCache;
+/// use std::time::Duration;
+///
+/// let cache = SearchCache::new("./.search_cache").ttl(Duration::from_secs(3600));
+/// assert_eq!(cache.ttl.as_secs(), 3600);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchCache {
+    /// Directory holding one JSON file per cached query.
+    pub dir: PathBuf,
+    /// How long a cached result is reused before the backend is asked again.
+    pub ttl: Duration,
+}
+
+impl SearchCache {
+    /// One week; research on prompting practice does not go stale quickly.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+    /// Creates a cache in `dir` with [`SearchCache::DEFAULT_TTL`].
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: Self::DEFAULT_TTL,
+        }
+    }
+
+    /// Sets how long cached results stay fresh.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+/// A cached search, as stored on disk.
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheEntry {
+    provider: String,
+    query: String,
+    /// Seconds since the Unix epoch.
+    stored_at: u64,
+    results: Vec<SearchResult>,
+}
+
+/// Wraps a [`SearchProvider`] and serves repeated queries from disk.
+///
+/// Queries are keyed on the backend's [identity](SearchProvider::identity) and the query with case and whitespace
+/// normalized, so `"Rust  Prompts"` and `"rust prompts"` share an entry. Failed searches
+/// are never cached. Problems reading or writing the cache are logged and otherwise
+/// ignored: the cache can only save a search, never cause one to fail.
+#[derive(Debug, Clone)]
+pub struct CachedProvider {
+    inner: Arc<dyn SearchProvider>,
+    cache: SearchCache,
+}
+
+impl CachedProvider {
+    /// Caches the results of `inner` according to `cache`.
+    pub fn new(inner: Arc<dyn SearchProvider>, cache: SearchCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Path of the cache file for `query`.
+    fn entry_path(&self, query: &str) -> PathBuf {
+        let normalized = normalize(query);
+        let mut hasher = Sha256::new();
+        hasher.update(self.inner.identity().as_bytes());
+        hasher.update([0]);
+        hasher.update(normalized.as_bytes());
+        self.cache.dir.join(format!("{:x}.json", hasher.finalize()))
+    }
+
+    /// Returns the cached results for `query` if present and still fresh.
+    async fn load(&self, path: &Path) -> Option<Vec<SearchResult>> {
+        let content = fs::read(path).await.ok()?;
+        let entry: CacheEntry = match serde_json::from_slice(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable search cache entry {:?}: {}", path, e);
+                return None;
+            }
+        };
+        let age = unix_now().saturating_sub(entry.stored_at);
+        (age < self.cache.ttl.as_secs()).then_some(entry.results)
+    }
+
+    async fn store(&self, path: &Path, query: &str, results: &[SearchResult]) {
+        let entry = CacheEntry {
+            provider: self.inner.identity(),
+            query: query.to_string(),
+            stored_at: unix_now(),
+            results: results.to_vec(),
+        };
+        let written = async {
+            fs::create_dir_all(&self.cache.dir).await?;
+            let content = serde_json::to_vec_pretty(&entry)?;
+            fs::write(path, content).await
+        };
+        if let Err(e) = written.await {
+            tracing::warn!("Failed to write search cache entry {:?}: {}", path, e);
+        }
+    }
+}
+
+impl SearchProvider for CachedProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn identity(&self) -> String {
+        self.inner.identity()
+    }
+
+    fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+        Box::pin(async move {
+            let path = self.entry_path(query);
+            if let Some(results) = self.load(&path).await {
+                tracing::info!("Search cache HIT: '{}' ({})", query, self.inner.name());
+                return Ok(results);
+            }
+            tracing::info!("Search cache MISS: '{}' ({})", query, self.inner.name());
+            let results = self.inner.search(query).await?;
+            self.store(&path, query, &results).await;
+            Ok(results)
+        })
+    }
+}
+
+/// Lowercases `query` and collapses runs of whitespace.
+fn normalize(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ScribeError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Counting {
+        calls: AtomicUsize,
+    }
+
+    impl SearchProvider for Counting {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+            Box::pin(async move {
+                if query == "fail" {
+                    return Err(ScribeError::Search {
+                        query: query.to_string(),
+                        cause: "down".to_string(),
+                    });
+                }
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![SearchResult::new(query, "https://example.com", "")])
+            })
+        }
+    }
+
+    fn temp_cache(name: &str) -> SearchCache {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        SearchCache::new(dir)
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("  Rust\t Prompts \n"), "rust prompts");
+    }
+
+    #[tokio::test]
+    async fn test_repeated_query_is_served_from_cache() {
+        let cache = temp_cache("rigscribe_search_cache_hit");
+        let inner = Arc::new(Counting::default());
+        let provider = CachedProvider::new(inner.clone(), cache.clone());
+
+        provider.search("Rust prompts").await.unwrap();
+        let results = provider.search("rust   PROMPTS").await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(results[0].title, "Rust prompts");
+        let _ = fs::remove_dir_all(cache.dir).await;
+    }
+
+    #[test]
+    fn test_entries_are_keyed_on_the_backend_identity() {
+        use crate::search::{LocalCorpusProvider, SearxngProvider};
+
+        let cache = SearchCache::new("unused");
+        let path = |provider: Arc<dyn SearchProvider>| {
+            CachedProvider::new(provider, cache.clone()).entry_path("rust")
+        };
+        assert_ne!(
+            path(Arc::new(SearxngProvider::new("http://a.example"))),
+            path(Arc::new(SearxngProvider::new("http://b.example")))
+        );
+        assert_ne!(
+            path(Arc::new(LocalCorpusProvider::new("./notes"))),
+            path(Arc::new(LocalCorpusProvider::new("./docs")))
+        );
+        assert_eq!(
+            path(Arc::new(SearxngProvider::new("http://a.example/"))),
+            path(Arc::new(SearxngProvider::new("http://a.example")))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refreshed() {
+        let cache = temp_cache("rigscribe_search_cache_ttl").ttl(Duration::ZERO);
+        let inner = Arc::new(Counting::default());
+        let provider = CachedProvider::new(inner.clone(), cache.clone());
+
+        provider.search("rust").await.unwrap();
+        provider.search("rust").await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        let _ = fs::remove_dir_all(cache.dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_not_cached() {
+        let cache = temp_cache("rigscribe_search_cache_fail");
+        let provider = CachedProvider::new(Arc::new(Counting::default()), cache.clone());
+
+        assert!(provider.search("fail").await.is_err());
+        assert!(!cache.dir.exists());
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use futures::future::BoxFuture;
+
+use crate::error::{Result, ScribeError};
+use crate::search::SearchProvider;
+use crate::types::SearchResult;
+use crate::utilities::unix_now;
+
+/// File extensions considered part of a local corpus.
+const CORPUS_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// Maximum number of documents returned per query.
+const MAX_DOCUMENTS: usize = 5;
+
+/// Maximum length, in characters, of the excerpt taken from each document.
+const MAX_EXCERPT_CHARS: usize = 4_000;
+
+/// Searches a local folder of Markdown and text files.
+///
+/// Documents are ranked by how often the query's terms occur in them, and the
+/// paragraphs mentioning those terms are returned. No network access is needed.
+#[derive(Debug, Clone)]
+pub struct LocalCorpusProvider {
+    root: PathBuf,
+}
+
+impl LocalCorpusProvider {
+    /// Creates a provider over the files below `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Lowercased query terms, ignoring very short words.
+    fn terms(query: &str) -> Vec<String> {
+        query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| term.chars().count() > 2)
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Collects every corpus file below `dir`.
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::collect_files(&path, files)?;
+            } else if path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| CORPUS_EXTENSIONS.contains(&ext))
+            {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the paragraphs of `text` that mention at least one term, up to the excerpt limit.
+    fn excerpt(text: &str, terms: &[String]) -> String {
+        let mut excerpt = String::new();
+        for paragraph in text.split("\n\n") {
+            let lower = paragraph.to_lowercase();
+            if !terms.iter().any(|term| lower.contains(term.as_str())) {
+                continue;
+            }
+            if excerpt.chars().count() + paragraph.chars().count() > MAX_EXCERPT_CHARS {
+                break;
+            }
+            excerpt.push_str(paragraph.trim());
+            excerpt.push_str("\n\n");
+        }
+        excerpt
+    }
+
+    fn search_blocking(root: &Path, query: &str) -> Result<Vec<SearchResult>> {
+        let terms = Self::terms(query);
+        let mut files = Vec::new();
+        Self::collect_files(root, &mut files).map_err(|e| ScribeError::Search {
+            query: query.to_string(),
+            cause: format!("failed to read local corpus {:?}: {}", root, e),
+        })?;
+
+        let mut ranked = Vec::new();
+        for path in files {
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                tracing::warn!("Skipping unreadable corpus file {:?}", path);
+                continue;
+            };
+            let lower = text.to_lowercase();
+            let score: usize = terms.iter().map(|term| lower.matches(term.as_str()).count()).sum();
+            if score > 0 {
+                ranked.push((score, path, text));
+            }
+        }
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+        let fetched_at = unix_now();
+        Ok(ranked
+            .into_iter()
+            .take(MAX_DOCUMENTS)
+            .map(|(_, path, text)| SearchResult {
+                title: path.strip_prefix(root).unwrap_or(&path).display().to_string(),
+                url: format!("file://{}", path.display()),
+                snippet: Self::excerpt(&text, &terms).trim_end().to_string(),
+                content: None,
+                fetched_at,
+            })
+            .collect())
+    }
+}
+
+impl SearchProvider for LocalCorpusProvider {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    /// The corpus folder, made absolute so that the same relative path used from two
+    /// working directories is not mistaken for one corpus.
+    fn identity(&self) -> String {
+        let root = std::path::absolute(&self.root).unwrap_or_else(|_| self.root.clone());
+        format!("local:{}", root.display())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Search`] if the corpus folder cannot be read.
+    fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+        let root = self.root.clone();
+        let query = query.to_string();
+        Box::pin(async move {
+            let task_query = query.clone();
+            tokio::task::spawn_blocking(move || Self::search_blocking(&root, &task_query))
+                .await
+                .map_err(|e| ScribeError::Search {
+                    query: query.clone(),
+                    cause: format!("local search task failed: {e}"),
+                })?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms_ignore_short_words() {
+        assert_eq!(LocalCorpusProvider::terms("a Rust CLI in go"), vec!["rust", "cli"]);
+    }
+
+    #[tokio::test]
+    async fn test_local_search_ranks_matching_files() {
+        let root = std::env::temp_dir().join("rigscribe_test_corpus");
+        let _ = tokio::fs::create_dir_all(root.join("nested")).await;
+        tokio::fs::write(root.join("rust.md"), "# Rust\n\nRust prompts need examples.\n\nUnrelated.")
+            .await
+            .unwrap();
+        tokio::fs::write(root.join("nested/python.txt"), "Python only.").await.unwrap();
+        tokio::fs::write(root.join("ignored.json"), "rust rust rust").await.unwrap();
+
+        let provider = LocalCorpusProvider::new(&root);
+        let results = provider.search("rust prompts").await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "rust.md");
+        assert!(results[0].url.starts_with("file://"));
+        assert!(results[0].snippet.contains("Rust prompts need examples."));
+        assert!(!results[0].snippet.contains("Unrelated."));
+
+        let _ = tokio::fs::remove_dir_all(root).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_search_missing_root() {
+        let provider = LocalCorpusProvider::new("/non/existent/corpus");
+        match provider.search("rust").await {
+            Err(ScribeError::Search { query, cause }) => {
+                assert_eq!(query, "rust");
+                assert!(cause.contains("/non/existent/corpus"));
+            }
+            other => panic!("Expected Search error, got {other:?}"),
+        }
+    }
+}
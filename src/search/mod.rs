@@ -0,0 +1,174 @@
+//! Search backends used by the [`WebSearcher`](crate::tools::web_searcher::WebSearcher) tool.
+//!
+//! The research step talks to a [`SearchProvider`]. Which provider is used is chosen
+//! through [`RigScribeConfig::search`](crate::RigScribeConfig) with a [`SearchBackend`]:
+//!
+//! * [`SearchBackend::SerpApi`] - Google results through SerpApi, with the page content of
+//!   each result (requires `SERPAPI_API_KEY`).
+//! * [`SearchBackend::Searxng`] - a self-hosted SearxNG instance.
+//! * [`SearchBackend::LocalCorpus`] - a folder of Markdown/text files, for air-gapped setups.
+//! * [`SearchBackend::Custom`] - any other implementation, e.g. a stub in tests.
+//!
+//! Any backend can be put behind an on-disk cache by setting
+//! [`RigScribeConfig::search_cache`](crate::RigScribeConfig) (see [`CachedProvider`]).
+
+pub mod cache;
+pub mod local;
+pub mod searxng;
+pub mod serpapi;
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use crate::error::Result;
+use crate::types::SearchResult;
+
+pub use cache::{CachedProvider, SearchCache};
+pub use local::LocalCorpusProvider;
+pub use searxng::SearxngProvider;
+pub use serpapi::SerpApiProvider;
+
+/// A source of research material for the agents.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::BoxFuture;
+/// use rigscribe::{Result, SearchResult, search::SearchProvider};
+///
+/// #[derive(Debug)]
+/// struct Canned;
+///
+/// impl SearchProvider for Canned {
+///     fn name(&self) -> &str {
+///         "canned"
+///     }
+///
+///     fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+///         Box::pin(async move {
+///             Ok(vec![SearchResult::new(query, "https://example.com", "No news.")])
+///         })
+///     }
+/// }
+/// ```
+pub trait SearchProvider: Send + Sync + fmt::Debug {
+    /// A short, stable name for the backend, used in logs.
+    fn name(&self) -> &str;
+
+    /// Identifies this particular backend, e.g. which instance or folder it searches.
+    ///
+    /// Two providers with the same identity must return the same results for a query;
+    /// [`CachedProvider`] keys its entries on it. Defaults to [`SearchProvider::name`], which
+    /// is enough for a custom provider with a name of its own.
+    fn identity(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Runs `query` and returns the findings, most relevant first.
+    fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>>;
+}
+
+/// Selects the [`SearchProvider`] used for research.
+#[derive(Debug, Clone)]
+pub enum SearchBackend {
+    /// Google results through [SerpApi](https://serpapi.com), the backend used since the
+    /// first release. Reads `SERPAPI_API_KEY`. See [`SerpApiProvider`].
+    SerpApi {
+        /// Whether the page behind each result is fetched as well, as the first release did.
+        fetch_pages: bool,
+    },
+    /// A self-hosted SearxNG instance with the JSON output format enabled.
+    Searxng {
+        /// Base URL of the instance, e.g. `http://localhost:8080`.
+        base_url: String,
+    },
+    /// A local folder of `.md`/`.txt` files.
+    LocalCorpus {
+        /// Root of the corpus; searched recursively.
+        root: PathBuf,
+    },
+    /// A caller-provided implementation.
+    Custom(Arc<dyn SearchProvider>),
+}
+
+/// What the `WebSearcher` does when the search backend fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchFailurePolicy {
+    /// Return the [`ScribeError`](crate::ScribeError) from the tool call, which aborts the run.
+    #[default]
+    Abort,
+    /// Log a warning and hand the model an empty result explaining that the search failed,
+    /// so the run continues without research input.
+    ReturnEmpty,
+}
+
+impl Default for SearchBackend {
+    /// [`SearchBackend::SerpApi`], fetching the result pages.
+    fn default() -> Self {
+        SearchBackend::SerpApi { fetch_pages: true }
+    }
+}
+
+impl SearchBackend {
+    /// Builds the provider for this backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::search::SearchBackend;
+    ///
+    /// let provider = SearchBackend::LocalCorpus { root: "./docs".into() }.provider();
+    /// assert_eq!(provider.name(), "local");
+    /// ```
+    pub fn provider(&self) -> Arc<dyn SearchProvider> {
+        match self {
+            SearchBackend::SerpApi { fetch_pages } => {
+                Arc::new(SerpApiProvider::default().fetch_pages(*fetch_pages))
+            }
+            SearchBackend::Searxng { base_url } => Arc::new(SearxngProvider::new(base_url.clone())),
+            SearchBackend::LocalCorpus { root } => Arc::new(LocalCorpusProvider::new(root.clone())),
+            SearchBackend::Custom(provider) => provider.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Stub;
+
+    impl SearchProvider for Stub {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+            Box::pin(async move { Ok(vec![SearchResult::new(query, "https://stub", "")]) })
+        }
+    }
+
+    #[test]
+    fn test_default_backend_is_serpapi() {
+        assert!(matches!(SearchBackend::default(), SearchBackend::SerpApi { fetch_pages: true }));
+        assert_eq!(SearchBackend::default().provider().name(), "serpapi");
+    }
+
+    #[test]
+    fn test_searxng_backend() {
+        let backend = SearchBackend::Searxng { base_url: "http://localhost:8080".into() };
+        assert_eq!(backend.provider().name(), "searxng");
+    }
+
+    #[tokio::test]
+    async fn test_custom_backend_is_used() {
+        let backend = SearchBackend::Custom(Arc::new(Stub));
+        let provider = backend.provider();
+        assert_eq!(provider.name(), "stub");
+        assert_eq!(provider.search("rust").await.unwrap()[0].title, "rust");
+    }
+}
@@ -0,0 +1,114 @@
+use futures::future::BoxFuture;
+use serde::Deserialize;
+
+use crate::error::{Result, ScribeError};
+use crate::search::SearchProvider;
+use crate::types::SearchResult;
+use crate::utilities::unix_now;
+
+/// Searches a self-hosted [SearxNG](https://docs.searxng.org/) instance.
+///
+/// The instance must have the `json` output format enabled in its `settings.yml`.
+#[derive(Debug, Clone)]
+pub struct SearxngProvider {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+/// The subset of SearxNG's JSON response we use.
+#[derive(Debug, Deserialize)]
+struct SearxngResponse {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearxngResult {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+impl SearxngProvider {
+    /// Creates a provider for the instance at `base_url` (e.g. `http://localhost:8080`).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Converts a SearxNG response into search results.
+    fn to_results(response: SearxngResponse) -> Vec<SearchResult> {
+        let fetched_at = unix_now();
+        response
+            .results
+            .into_iter()
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                snippet: result.content,
+                content: None,
+                fetched_at,
+            })
+            .collect()
+    }
+}
+
+impl SearchProvider for SearxngProvider {
+    fn name(&self) -> &str {
+        "searxng"
+    }
+
+    fn identity(&self) -> String {
+        format!("searxng:{}", self.base_url)
+    }
+
+    fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+        Box::pin(async move {
+            let fail = |cause: String| ScribeError::Search {
+                query: query.to_string(),
+                cause,
+            };
+            let body = self
+                .client
+                .get(format!("{}/search", self.base_url))
+                .query(&[("q", query), ("format", "json")])
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| fail(e.to_string()))?
+                .text()
+                .await
+                .map_err(|e| fail(e.to_string()))?;
+            let response: SearxngResponse =
+                serde_json::from_str(&body).map_err(|e| fail(e.to_string()))?;
+            Ok(Self::to_results(response))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_trailing_slash_is_trimmed() {
+        let provider = SearxngProvider::new("http://localhost:8080/");
+        assert_eq!(provider.base_url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_response_to_results() {
+        let json = r#"{"results": [{"title": "Prompting", "url": "https://example.com", "content": "Be specific."}]}"#;
+        let response: SearxngResponse = serde_json::from_str(json).unwrap();
+        let results = SearxngProvider::to_results(response);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Prompting");
+        assert_eq!(results[0].url, "https://example.com");
+        assert_eq!(results[0].snippet, "Be specific.");
+    }
+}
@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use futures::future::{BoxFuture, join_all};
+use html_to_markdown_rs::{ConversionOptions, PreprocessingOptions, convert};
+use serde::Deserialize;
+
+use crate::error::{Result, ScribeError};
+use crate::search::SearchProvider;
+use crate::types::SearchResult;
+use crate::utilities::{require_env, unix_now};
+
+/// Endpoint of the SerpApi Google search API.
+const SEARCH_ENDPOINT: &str = "https://serpapi.com/search.json";
+
+/// Maximum length, in characters, of the page content kept per result.
+const MAX_PAGE_CHARS: usize = 8_000;
+
+/// How long fetching a single result page may take.
+const PAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Searches Google through [SerpApi](https://serpapi.com) and returns the organic results.
+///
+/// By default the page behind each result is fetched too and converted to Markdown, so
+/// the agents read the pages themselves rather than only their snippets. Pages that cannot
+/// be fetched keep just their snippet.
+///
+/// Requires the `SERPAPI_API_KEY` environment variable; `SERPER_API_KEY`, the name used by
+/// earlier releases, is still read when it is not set.
+#[derive(Debug, Clone)]
+pub struct SerpApiProvider {
+    client: reqwest::Client,
+    fetch_pages: bool,
+}
+
+impl Default for SerpApiProvider {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            fetch_pages: true,
+        }
+    }
+}
+
+/// The subset of the API response we use.
+#[derive(Debug, Deserialize)]
+struct SerpApiResponse {
+    #[serde(default)]
+    organic_results: Vec<OrganicResult>,
+    /// Set by the API instead of results, e.g. for an invalid key or exhausted quota.
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrganicResult {
+    title: String,
+    link: String,
+    #[serde(default)]
+    snippet: Option<String>,
+}
+
+impl SerpApiProvider {
+    /// Sets whether the page behind each result is fetched (the default) or only the
+    /// snippets are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::search::SerpApiProvider;
+    ///
+    /// let snippets_only = SerpApiProvider::default().fetch_pages(false);
+    /// ```
+    pub fn fetch_pages(mut self, fetch_pages: bool) -> Self {
+        self.fetch_pages = fetch_pages;
+        self
+    }
+
+    /// Converts an API response into search results.
+    fn to_results(query: &str, response: SerpApiResponse) -> Result<Vec<SearchResult>> {
+        if let Some(error) = response.error {
+            return Err(ScribeError::Search {
+                query: query.to_string(),
+                cause: error,
+            });
+        }
+        let fetched_at = unix_now();
+        Ok(response
+            .organic_results
+            .into_iter()
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.link,
+                snippet: result.snippet.unwrap_or_default(),
+                content: None,
+                fetched_at,
+            })
+            .collect())
+    }
+
+    /// Fetches the page at `url` as Markdown, or `None` if it cannot be read.
+    async fn page_content(&self, url: &str) -> Option<String> {
+        let response = self
+            .client
+            .get(url)
+            .timeout(PAGE_TIMEOUT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        let body = match response {
+            Ok(response) => response.text().await,
+            Err(e) => Err(e),
+        };
+        match body {
+            Ok(body) => page_to_markdown(&body),
+            Err(e) => {
+                tracing::warn!("Skipping the content of {}: {}", url, e.without_url());
+                None
+            }
+        }
+    }
+}
+
+/// Converts a fetched page to Markdown without navigation, forms and images, cut to
+/// [`MAX_PAGE_CHARS`]. Plain-text bodies are kept as they are.
+fn page_to_markdown(body: &str) -> Option<String> {
+    let looks_like_html = body.trim_start().starts_with('<');
+    let markdown = if looks_like_html {
+        let options = ConversionOptions {
+            skip_images: true,
+            preprocessing: PreprocessingOptions {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        convert(body, Some(options)).ok()?
+    } else {
+        body.to_string()
+    };
+    let markdown = markdown.trim();
+    (!markdown.is_empty()).then(|| markdown.chars().take(MAX_PAGE_CHARS).collect())
+}
+
+/// The API key, from `SERPAPI_API_KEY` or the legacy `SERPER_API_KEY`.
+fn api_key() -> Result<String> {
+    require_env("SERPAPI_API_KEY").or_else(|e| require_env("SERPER_API_KEY").map_err(|_| e))
+}
+
+impl SearchProvider for SerpApiProvider {
+    fn name(&self) -> &str {
+        "serpapi"
+    }
+
+    fn identity(&self) -> String {
+        let mode = if self.fetch_pages { "pages" } else { "snippets" };
+        format!("serpapi:{mode}")
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Config`] if `SERPAPI_API_KEY` is missing, and
+    /// [`ScribeError::Search`] if the API call fails. Pages that cannot be fetched are not
+    /// errors.
+    fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+        Box::pin(async move {
+            let api_key = api_key()?;
+            let fail = |cause: String| ScribeError::Search {
+                query: query.to_string(),
+                cause,
+            };
+            let body = self
+                .client
+                .get(SEARCH_ENDPOINT)
+                .query(&[
+                    ("engine", "google"),
+                    ("q", query),
+                    ("hl", "en"),
+                    ("gl", "us"),
+                    ("google_domain", "google.com"),
+                    ("api_key", api_key.as_str()),
+                ])
+                .send()
+                .await
+                .map_err(|e| fail(e.without_url().to_string()))?
+                .text()
+                .await
+                .map_err(|e| fail(e.without_url().to_string()))?;
+            let response: SerpApiResponse =
+                serde_json::from_str(&body).map_err(|e| fail(e.to_string()))?;
+            let mut results = Self::to_results(query, response)?;
+            if self.fetch_pages {
+                let pages = join_all(results.iter().map(|result| self.page_content(&result.url))).await;
+                for (result, content) in results.iter_mut().zip(pages) {
+                    result.content = content;
+                }
+            }
+            Ok(results)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serpapi_name() {
+        assert_eq!(SerpApiProvider::default().name(), "serpapi");
+    }
+
+    #[test]
+    fn test_response_to_results() {
+        let json = r#"{"organic_results": [{"title": "Guide", "link": "https://example.com", "position": 1, "snippet": "Be specific."}]}"#;
+        let response: SerpApiResponse = serde_json::from_str(json).unwrap();
+        let results = SerpApiProvider::to_results("q", response).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].url, "https://example.com");
+        assert_eq!(results[0].snippet, "Be specific.");
+        assert!(results[0].content.is_none());
+    }
+
+    #[test]
+    fn test_api_error_is_search_error() {
+        let json = r#"{"error": "Invalid API key."}"#;
+        let response: SerpApiResponse = serde_json::from_str(json).unwrap();
+        match SerpApiProvider::to_results("q", response) {
+            Err(ScribeError::Search { cause, .. }) => assert_eq!(cause, "Invalid API key."),
+            _ => panic!("Expected Search error"),
+        }
+    }
+
+    #[test]
+    fn test_page_to_markdown() {
+        let html = "<html><body><nav><a href=\"/\">Home</a></nav>\
+                    <h1>Prompting</h1><p>Give <b>examples</b>.</p><img src=\"x.png\"></body></html>";
+        let markdown = page_to_markdown(html).unwrap();
+        assert!(markdown.contains("Prompting"));
+        assert!(markdown.contains("**examples**"));
+        assert!(!markdown.contains("x.png"));
+
+        assert_eq!(page_to_markdown("plain text").as_deref(), Some("plain text"));
+        assert!(page_to_markdown("  ").is_none());
+        let long = "y".repeat(MAX_PAGE_CHARS + 10);
+        assert_eq!(page_to_markdown(&long).unwrap().chars().count(), MAX_PAGE_CHARS);
+    }
+
+    // TODO (UNTESTABLE): test_serpapi_search
+    // Requires a live API key and internet access.
+}
@@ -0,0 +1,314 @@
+//! Structured access to the Markdown sections of a system prompt.
+//!
+//! Optimized prompts are written as Markdown sections (`# Persona`, `# Primary Task`,
+//! `# Constraints`, ...), but an [`Artifact`] stores the prompt as one string.
+//! [`Sections::parse`] splits it at its headings so that single sections can be read,
+//! replaced, removed or reordered, and [`Sections`]' `Display` impl writes the prompt back.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ScribeError};
+use crate::types::Artifact;
+
+/// One section of a prompt: a heading and the text up to the next heading.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Section {
+    /// The heading level, 1 for `#` to 6 for `######`; 0 for the text before the first
+    /// heading.
+    pub level: usize,
+    /// The heading text, without the `#`s; empty for the text before the first heading.
+    pub heading: String,
+    /// The text under the heading, verbatim.
+    pub body: String,
+}
+
+impl Section {
+    /// Creates a section with the given heading level (1 to 6), heading and body.
+    pub fn new(level: usize, heading: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            level: level.clamp(1, 6),
+            heading: heading.into().trim().to_string(),
+            body: body.into(),
+        }
+    }
+
+    /// Whether the section is the text before the first heading.
+    pub fn is_preamble(&self) -> bool {
+        self.level == 0
+    }
+
+    /// Whether the section's heading is `heading`, ignoring case and surrounding spaces.
+    fn is_named(&self, heading: &str) -> bool {
+        !self.is_preamble() && self.heading.to_lowercase() == heading.trim().to_lowercase()
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_preamble() {
+            writeln!(f, "{} {}", "#".repeat(self.level), self.heading)?;
+        }
+        f.write_str(&self.body)
+    }
+}
+
+/// A prompt split into its Markdown sections.
+///
+/// Parsing and writing back gives the original prompt, as long as its headings are written
+/// `# Heading` with a single space.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::sections::Sections;
+///
+/// let prompt = "You are a support agent.\n# Constraints\n- Be brief.\n# Output Format\nPlain text.\n";
+/// let mut sections = Sections::parse(prompt);
+/// assert_eq!(sections.headings(), vec!["Constraints", "Output Format"]);
+/// assert_eq!(sections.to_string(), prompt);
+///
+/// sections.replace("output format", "JSON only.").unwrap();
+/// sections.reorder(&["Output Format", "Constraints"]).unwrap();
+/// assert_eq!(
+///     sections.to_string(),
+///     "You are a support agent.\n# Output Format\nJSON only.\n# Constraints\n- Be brief.\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Sections {
+    sections: Vec<Section>,
+}
+
+impl Sections {
+    /// Splits `prompt` at its Markdown headings. Text before the first heading becomes a
+    /// preamble section; `#` lines inside code fences are not headings.
+    pub fn parse(prompt: &str) -> Self {
+        let mut sections = vec![Section { level: 0, heading: String::new(), body: String::new() }];
+        let mut in_fence = false;
+        for line in prompt.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            let heading = &trimmed[level..];
+            if !in_fence && (1..=6).contains(&level) && heading.starts_with([' ', '\t']) {
+                sections.push(Section {
+                    level,
+                    heading: heading.trim().to_string(),
+                    body: String::new(),
+                });
+                continue;
+            }
+            let section = sections.last_mut().expect("there is always a section");
+            section.body.push_str(line);
+        }
+        if sections[0].body.is_empty() {
+            sections.remove(0);
+        }
+        Self { sections }
+    }
+
+    /// The number of sections, including the preamble.
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Whether the prompt was empty.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// The sections in order, including the preamble.
+    pub fn iter(&self) -> std::slice::Iter<'_, Section> {
+        self.sections.iter()
+    }
+
+    /// The headings in order, without the preamble.
+    pub fn headings(&self) -> Vec<&str> {
+        self.iter()
+            .filter(|section| !section.is_preamble())
+            .map(|section| section.heading.as_str())
+            .collect()
+    }
+
+    /// The text before the first heading, if any.
+    pub fn preamble(&self) -> Option<&Section> {
+        self.sections.first().filter(|section| section.is_preamble())
+    }
+
+    /// The position of the first section named `heading`, ignoring case.
+    pub fn position(&self, heading: &str) -> Option<usize> {
+        self.sections.iter().position(|section| section.is_named(heading))
+    }
+
+    /// The first section named `heading`, ignoring case.
+    pub fn get(&self, heading: &str) -> Option<&Section> {
+        self.position(heading).map(|index| &self.sections[index])
+    }
+
+    /// The first section named `heading`, ignoring case, for editing.
+    pub fn get_mut(&mut self, heading: &str) -> Option<&mut Section> {
+        self.position(heading).map(|index| &mut self.sections[index])
+    }
+
+    /// Replaces the body of the section named `heading`, keeping its heading.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if there is no such section.
+    pub fn replace(&mut self, heading: &str, body: impl Into<String>) -> Result<()> {
+        let mut body = body.into();
+        if !body.is_empty() && !body.ends_with('\n') {
+            body.push('\n');
+        }
+        let section = self.get_mut(heading).ok_or_else(|| missing(heading))?;
+        section.body = body;
+        Ok(())
+    }
+
+    /// Appends `section` at the end.
+    pub fn push(&mut self, section: Section) {
+        self.sections.push(section);
+    }
+
+    /// Inserts `section` at `index`, after the preamble at the earliest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, section: Section) {
+        let first = usize::from(self.preamble().is_some());
+        self.sections.insert(index.max(first), section);
+    }
+
+    /// Removes and returns the first section named `heading`, ignoring case.
+    pub fn remove(&mut self, heading: &str) -> Option<Section> {
+        self.position(heading).map(|index| self.sections.remove(index))
+    }
+
+    /// Moves the sections named in `order` to the front, in that order, after the preamble.
+    /// The other sections follow in their current order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if a heading in `order` names no section; the
+    /// sections are left as they were.
+    pub fn reorder(&mut self, order: &[&str]) -> Result<()> {
+        if let Some(unknown) = order.iter().find(|heading| self.position(heading).is_none()) {
+            return Err(missing(unknown));
+        }
+        let mut rest = std::mem::take(&mut self.sections);
+        let mut sections = Vec::with_capacity(rest.len());
+        if rest.first().is_some_and(Section::is_preamble) {
+            sections.push(rest.remove(0));
+        }
+        for heading in order {
+            if let Some(index) = rest.iter().position(|section| section.is_named(heading)) {
+                sections.push(rest.remove(index));
+            }
+        }
+        sections.append(&mut rest);
+        self.sections = sections;
+        Ok(())
+    }
+}
+
+fn missing(heading: &str) -> ScribeError {
+    ScribeError::Validation(format!("The prompt has no '{}' section", heading.trim()))
+}
+
+impl fmt::Display for Sections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.sections.len().saturating_sub(1);
+        for (index, section) in self.sections.iter().enumerate() {
+            write!(f, "{section}")?;
+            // A moved last section may lack its newline; the next heading needs its own line.
+            if index < last && !section.body.is_empty() && !section.body.ends_with('\n') {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Sections {
+    type Item = &'a Section;
+    type IntoIter = std::slice::Iter<'a, Section>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Artifact {
+    /// Splits the system prompt into its Markdown sections. See [`Sections`].
+    pub fn sections(&self) -> Sections {
+        Sections::parse(&self.system_prompt)
+    }
+
+    /// Replaces the system prompt with `sections`, written back as Markdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Artifact, sections::Section};
+    ///
+    /// let mut artifact = Artifact::new("# Persona\nYou are terse.\n", "");
+    /// let mut sections = artifact.sections();
+    /// sections.push(Section::new(1, "Output Format", "Plain text.\n"));
+    /// artifact.set_sections(&sections);
+    /// assert_eq!(artifact.system_prompt, "# Persona\nYou are terse.\n# Output Format\nPlain text.\n");
+    /// ```
+    pub fn set_sections(&mut self, sections: &Sections) {
+        self.system_prompt = sections.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROMPT: &str = "You help users.\n\n# Persona\nFriendly.\n\n## Tone\nWarm.\n# Output Format\n```md\n# Not a heading\n```\nEnd.";
+
+    #[test]
+    fn test_parse_round_trips() {
+        let sections = Sections::parse(PROMPT);
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections.preamble().unwrap().body, "You help users.\n\n");
+        assert_eq!(sections.headings(), vec!["Persona", "Tone", "Output Format"]);
+        assert_eq!(sections.get("tone").unwrap().level, 2);
+        assert!(sections.get("Output Format").unwrap().body.contains("# Not a heading"));
+        assert_eq!(sections.to_string(), PROMPT);
+        assert!(Sections::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_edit_sections() {
+        let mut sections = Sections::parse(PROMPT);
+        sections.replace(" persona ", "Formal.").unwrap();
+        assert_eq!(sections.get("Persona").unwrap().body, "Formal.\n");
+        assert!(matches!(
+            sections.replace("Examples", "None."),
+            Err(ScribeError::Validation(_))
+        ));
+
+        assert_eq!(sections.remove("Tone").unwrap().body, "Warm.\n");
+        sections.insert(0, Section::new(1, "Primary Task", "Answer questions.\n"));
+        assert_eq!(sections.headings(), vec!["Primary Task", "Persona", "Output Format"]);
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut sections = Sections::parse(PROMPT);
+        sections.reorder(&["Output Format", "Persona"]).unwrap();
+        assert_eq!(sections.headings(), vec!["Output Format", "Persona", "Tone"]);
+        // The formerly last section gets the newline it needs before the next heading.
+        assert!(sections.to_string().contains("End.\n# Persona\n"));
+
+        assert!(sections.reorder(&["Persona", "Missing"]).is_err());
+        assert_eq!(sections.headings(), vec!["Output Format", "Persona", "Tone"]);
+    }
+}
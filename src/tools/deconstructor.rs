@@ -1,19 +1,47 @@
-use crate::types::{Intent, Specification, MODEL};
+use crate::types::{Attachments, Intent, RigScribeConfig, Specification};
 use crate::error::{Result, ScribeError};
 use crate::utilities::require_env;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use rig::providers::gemini::Client;
-use serde::{Deserialize, Serialize};
 use rig::client::ProviderClient;
 use rig::prelude::*; // Needed for .prompt() method
 
 /// A tool that analyzes a raw user prompt to extract key constraints and goals.
 ///
 /// This tool uses a specialized "Senior Solution Architect" agent to process the
-/// [`Intent`] and produce a structured [`Specification`].
-#[derive(Serialize, Deserialize)]
-pub struct Deconstructor;
+/// [`Intent`] and produce a structured [`Specification`]. The intent's
+/// [`Attachments`] are treated as ground truth.
+#[derive(Debug, Clone, Default)]
+pub struct Deconstructor {
+    config: RigScribeConfig,
+    attachments: Attachments,
+}
+
+impl Deconstructor {
+    /// Creates a `Deconstructor` that uses the given configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{tools::deconstructor::Deconstructor, RigScribeConfig};
+    ///
+    /// let tool = Deconstructor::new(RigScribeConfig::default());
+    /// ```
+    pub fn new(config: RigScribeConfig) -> Self {
+        Self {
+            config,
+            attachments: Attachments::default(),
+        }
+    }
+
+    /// Uses `attachments` for intents that arrive without any, e.g. when the Prompt Officer
+    /// passes on only the request text.
+    pub fn with_attachments(mut self, attachments: Attachments) -> Self {
+        self.attachments = attachments;
+        self
+    }
+}
 
 impl Tool for Deconstructor {
     const NAME: &'static str = "Deconstructor";
@@ -36,7 +64,7 @@ impl Tool for Deconstructor {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let tool = Deconstructor;
+    ///     let tool = Deconstructor::default();
     ///     let def = tool.definition("".to_string()).await;
     ///     assert_eq!(def.name, "Deconstructor");
     /// }
@@ -46,8 +74,8 @@ impl Tool for Deconstructor {
         let parameters = serde_json::to_value(schema).unwrap();
         ToolDefinition {
             name: "Deconstructor".to_string(),
-            description: "this tools take a raw prompte and give back it Specification include goal and constrian".to_string(),
-            parameters: parameters,
+            description: "this tools take a raw prompte and give back it Specification include goal, audience, inputs, output format, constraints, risks, assumptions, success criteria and open questions".to_string(),
+            parameters,
         }
     }
 
@@ -70,7 +98,7 @@ impl Tool for Deconstructor {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let tool = Deconstructor;
+    ///     let tool = Deconstructor::default();
     ///     let intent = Intent::new("Make a game").unwrap();
     ///     // Requires GEMINI_API_KEY
     ///     let spec = tool.call(intent).await.unwrap();
@@ -79,33 +107,37 @@ impl Tool for Deconstructor {
     /// ```
     async fn call(&self, args: Self::Args) -> Result<Self::Output> {
         tracing::info!("[Tool Calling]-> Deconstructor with args: {:?}", args);
+        let mut args = args;
+        if args.attachments.is_empty() {
+            args.attachments = self.attachments.clone();
+        }
         require_env("GEMINI_API_KEY")?;
         let client = Client::from_env();
         let architect = client
-            .agent(MODEL)
+            .agent(self.config.model)
             .preamble(
                 "\n                Role: Senior Solution Architect\n\
-                Task: Extract constraints and risks and main goal of given request\n\
-                Output: A short bullet list, no prose\n                ",
+                Task: Extract the main goal, target audience, inputs, output format, constraints, \
+                negative constraints, risks, assumptions, success criteria and open questions of given request\n\
+                Output: A short bullet list per heading, no prose. Leave a heading empty rather than guess. \
+                Only ask open questions whose answers would materially change the result. \
+                Anything the requester attached is ground truth: derive inputs and output format from \
+                the samples, copy hard requirements into the constraints verbatim and do not ask \
+                about what the attachments already answer\n                ",
             )
             .build();
         
-        let mut stream = crate::agents::multi_turn_prompt(architect, args.text.clone(), Vec::new()).await;
-        let mut full_response = String::new();
-        while let Some(res) = futures::StreamExt::next(&mut stream).await {
-             match res {
-                Ok(text) => {
-                     print!("{}", text.text);
-                     use std::io::Write;
-                     let _ = std::io::stdout().flush();
-                     full_response.push_str(&text.text);
-                }
-                Err(e) => return Err(ScribeError::ProtocolViolation(e.to_string())),
-            }
-        }
-        println!();
-        
-        let spec_extractor = client.extractor::<Specification>(MODEL).build();
+        let stream = crate::agents::multi_turn_prompt_with_budget(
+            architect,
+            args.full_text(),
+            Vec::new(),
+            self.config.context_budget.clone(),
+        )
+        .await;
+        let full_response =
+            crate::agents::collect_answer(stream, self.config.observer.as_ref(), Self::NAME, None).await?;
+
+        let spec_extractor = client.extractor::<Specification>(self.config.model).build();
         let spec = spec_extractor.extract(full_response).await?;
 
         tracing::debug!("Deconstructor extracted spec: {:?}", spec);
@@ -119,7 +151,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_deconstructor_definition() {
-        let tool = Deconstructor;
+        let tool = Deconstructor::default();
         let def = tool.definition("".into()).await;
         assert_eq!(def.name, "Deconstructor");
         // Verify parameter schema includes 'text' field
@@ -1,3 +1,9 @@
 pub mod deconstructor;
+pub mod example_generator;
+pub mod injection_auditor;
 pub mod prompt_reviewer;
+pub mod prompt_tester;
+pub mod registry;
+pub mod submit_prompt;
+pub mod translator;
 pub mod web_searcher;
@@ -1,9 +1,12 @@
-use crate::types::{Intent, Specification, Artifact, MODEL};
+use crate::types::{Intent, Specification, Artifact, Attachments, CritiqueReport, ReviewLoop, RigScribeConfig, TemplateVariable};
 use crate::error::{Result, ScribeError};
 use crate::utilities::require_env;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use rig::providers::gemini::Client;
+use crate::agents::run_log::RunLog;
+use crate::lint::Linter;
+use crate::tools::prompt_tester::PromptTester;
 use crate::tools::web_searcher::WebSearcher;
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
@@ -19,12 +22,63 @@ pub struct PromptReviewerArgs {
     pub(crate) spec: Specification,
 }
 
+/// What the reviewer's answer is extracted into.
+#[derive(Deserialize, Debug, Clone, Serialize, JsonSchema)]
+struct ReviewOutcome {
+    /// The critique of the draft.
+    #[schemars(description = "The critique of the draft, with every deficit found.")]
+    critique: CritiqueReport,
+    /// The rewritten prompt.
+    #[schemars(description = "The full rewritten system prompt, verbatim.")]
+    revised_prompt: String,
+}
+
 /// A tool that critically evaluates and refines a draft prompt.
 ///
 /// This tool acts as a "Prompt Officer," using web research to find best practices
-/// and then iteratively improving the prompt to meet the [`Specification`].
-#[derive(Serialize, Deserialize)]
-pub struct PromptReviewer;
+/// and then iteratively improving the prompt to meet the [`Specification`]. The returned
+/// [`Artifact`] carries the critique of the draft in [`Artifact::review`].
+///
+/// With [`RigScribeConfig::review_loop`] set, each rewrite is reviewed again until it is
+/// good enough (see [`ReviewLoop`]); the best-scoring revision is returned and
+/// [`Artifact::score_history`] lists the score of every reviewed revision.
+#[derive(Debug, Clone, Default)]
+pub struct PromptReviewer {
+    config: RigScribeConfig,
+    log: RunLog,
+    attachments: Attachments,
+}
+
+impl PromptReviewer {
+    /// Creates a `PromptReviewer` that uses the given configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{tools::prompt_reviewer::PromptReviewer, RigScribeConfig};
+    ///
+    /// let tool = PromptReviewer::new(RigScribeConfig::default());
+    /// ```
+    pub fn new(config: RigScribeConfig) -> Self {
+        Self {
+            config,
+            log: RunLog::default(),
+            attachments: Attachments::default(),
+        }
+    }
+
+    /// Records the reviewer's research in `log` instead of a private one.
+    pub fn with_log(mut self, log: RunLog) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Checks drafts against `attachments` when the intent it is called with has none.
+    pub fn with_attachments(mut self, attachments: Attachments) -> Self {
+        self.attachments = attachments;
+        self
+    }
+}
 
 impl Tool for PromptReviewer {
     const NAME: &'static str = "PromptReviewer";
@@ -43,7 +97,7 @@ impl Tool for PromptReviewer {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let tool = PromptReviewer;
+    ///     let tool = PromptReviewer::default();
     ///     let def = tool.definition("".into()).await;
     ///     assert_eq!(def.name, "PromptReviewer");
     /// }
@@ -73,54 +127,128 @@ impl Tool for PromptReviewer {
         tracing::info!("[Tool Calling]-> PromptReviewer with args: {:?}", args);
         require_env("GEMINI_API_KEY")?;
         let client = Client::from_env();
+        let review_loop = self.config.review_loop.unwrap_or(ReviewLoop {
+            max_iterations: 1,
+            ..Default::default()
+        });
+
+        // revisions[k] is revision k (0 is the draft); critiques[k] scored revision k and
+        // produced revision k + 1.
+        let attachments = if args.intent.attachments.is_empty() {
+            &self.attachments
+        } else {
+            &args.intent.attachments
+        };
+        let mut revisions = vec![args.intent.text.clone()];
+        let mut critiques: Vec<CritiqueReport> = Vec::new();
+        loop {
+            let draft = revisions.last().cloned().unwrap_or_default();
+            let outcome = self
+                .review_once(&client, &args.spec, attachments, &args.intent.variables, &draft)
+                .await?;
+            critiques.push(outcome.critique.normalized());
+            revisions.push(outcome.revised_prompt);
+
+            let scores: Vec<u8> = critiques.iter().map(|critique| critique.score).collect();
+            tracing::info!("PromptReviewer round {}: score {:?}", scores.len(), scores.last());
+            if review_loop.should_stop(&scores) {
+                break;
+            }
+        }
+
+        let scores: Vec<u8> = critiques.iter().map(|critique| critique.score).collect();
+        let best = ReviewLoop::pick(&scores);
+        // critiques[best - 1] produced revision `best`; a kept draft takes critiques[0], which scored it.
+        let critique = critiques.swap_remove(best.saturating_sub(1));
+        self.log.record_review(critique.clone());
+        self.log.record_score_history(scores.clone());
+
+        let mut artifact = Artifact::new(revisions.swap_remove(best), Self::NAME);
+        artifact.sources = self.log.sources();
+        artifact.review = Some(critique);
+        artifact.score_history = scores;
+
+        tracing::debug!("PromptReviewer produced artifact: {:?}", artifact);
+        Ok(artifact)
+    }
+}
+
+impl PromptReviewer {
+    /// Runs one round of review: critiques `draft`, a template declaring `variables`, and
+    /// rewrites it.
+    async fn review_once(
+        &self,
+        client: &Client,
+        spec: &Specification,
+        attachments: &Attachments,
+        variables: &[TemplateVariable],
+        draft: &str,
+    ) -> Result<ReviewOutcome> {
         let system_prompt_json = include_str!("../../data/prompt_officer.json");
         let artifact: Artifact = serde_json::from_str(system_prompt_json)
              .map_err(|e| ScribeError::Validation(format!("Failed to parse embedded prompt_officer.json: {}", e)))?;
         let system_prompt = artifact.system_prompt;
-        let prompt_reviewer = client.agent(MODEL)
+        let prompt_reviewer = client.agent(self.config.model)
             .preamble(system_prompt.as_str())
-            .tool(WebSearcher)
+            .tool(WebSearcher::from_config(&self.config).with_log(self.log.clone()))
+            .tool(PromptTester::new(self.config.clone()))
             .build();
         
+        let lint_notes = lint_notes(draft, variables);
+        let attachment_notes = if attachments.is_empty() {
+            String::new()
+        } else {
+            format!("\nAttached by the requester (the draft must fit this real data):\n{}\n", attachments.to_markdown())
+        };
         let input = format!(
-            "\n        Critisize following prompt base on given property:\n        Goal:\n{}
-
-Constraints:
-{}
+            "\n        Critisize following prompt base on given property:\n{}{}
 
 Draft:
 {}
-
+{}
 
         Instruction: Be highly critical and pessimistic. 
         1. You MUST first use the 'WebSearcher' tool to research state-of-the-art prompt engineering techniques and best practices specifically for this type of task.
-        2. Use the search results to find every deficit in the draft.
-        3. Rewrite the prompt to be flawless.
+        2. Use the 'PromptTester' tool to run the draft on sample inputs (pass the specification above so it can generate them).
+        3. Use the search results and the test outputs to find every deficit in the draft. Quote test outputs as evidence where they show one, and confirm or dismiss every static lint finding. If the requester attached samples, documents or hard requirements, a draft that would not produce the expected outputs, contradicts a document or misses a requirement has a high severity deficit.
+        4. Rewrite the prompt to be flawless.
+
+        Output: This supersedes your usual output mandate. Answer in two parts:
+        ## Critique
+        An overall score of the draft from 0 to 100, a one-sentence verdict, then one entry per deficit with its category (safety, clarity, constraints, output_format, structure, examples, other), severity (low, medium, high, critical), the offending text quoted verbatim from the draft (empty if something is missing) and the fix you applied.
+        ## Revised Prompt
+        The rewritten prompt, and nothing else.
         \n",
-            args.spec.goal, args.spec.constraints, args.intent.text
+            spec.to_markdown(), attachment_notes, draft, lint_notes
         );
 
-        let mut stream = crate::agents::multi_turn_prompt(prompt_reviewer, input, Vec::new()).await;
-        let mut full_response = String::new();
-        while let Some(res) = futures::StreamExt::next(&mut stream).await {
-             match res {
-                Ok(text) => {
-                     print!("{}", text.text);
-                     use std::io::Write;
-                     let _ = std::io::stdout().flush();
-                     full_response.push_str(&text.text);
-                }
-                Err(e) => return Err(ScribeError::ProtocolViolation(e.to_string())),
-            }
-        }
-        println!();
+        let stream = crate::agents::multi_turn_prompt_with_budget(
+            prompt_reviewer,
+            input,
+            Vec::new(),
+            self.config.context_budget.clone(),
+        )
+        .await;
+        // Only the answer after the last research call holds the rewrite.
+        let full_response =
+            crate::agents::collect_answer(stream, self.config.observer.as_ref(), Self::NAME, Some(&self.log))
+                .await?;
 
-        let artifact_extractor = client.extractor::<Artifact>(MODEL).build();
-        let artifact = artifact_extractor.extract(full_response).await?;
+        let review_extractor = client.extractor::<ReviewOutcome>(self.config.model).build();
+        Ok(review_extractor.extract(full_response).await?)
+    }
+}
 
-        tracing::debug!("PromptReviewer produced artifact: {:?}", artifact);
-        Ok(artifact)
+/// Cheap deterministic pre-check; the reviewer confirms and fixes what it flags. The
+/// placeholders of the declared `variables` are expected and not flagged.
+fn lint_notes(draft: &str, variables: &[TemplateVariable]) -> String {
+    let findings = Linter::default().lint_template(draft, variables);
+    tracing::info!("PromptReviewer pre-check found {} lint findings", findings.len());
+    if findings.is_empty() {
+        return String::new();
     }
+    let lines: Vec<String> = findings.iter().map(|finding| format!("- {finding}")).collect();
+    format!("\nStatic lint findings (line:column severity [rule] message):\n{}\n", lines.join("\n"))
 }
 
 #[cfg(test)]
@@ -129,11 +257,28 @@ mod tests {
 
     #[tokio::test]
     async fn test_prompt_reviewer_definition() {
-        let tool = PromptReviewer;
+        let tool = PromptReviewer::default();
         let def = tool.definition("".into()).await;
         assert_eq!(def.name, "PromptReviewer");
         let params = def.parameters.to_string();
         assert!(params.contains("intent"));
         assert!(params.contains("spec"));
     }
+
+    #[test]
+    fn test_lint_notes_skip_declared_variables() {
+        let draft = "Greet {{user_name}} from {{company}}. Respond in JSON.";
+        let notes = lint_notes(draft, &[TemplateVariable::new("user_name", "Who to greet.")]);
+        assert!(notes.contains("{{company}}"));
+        assert!(!notes.contains("{{user_name}}"));
+        assert!(lint_notes("Respond in JSON.", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_review_outcome_schema() {
+        let schema = serde_json::to_string(&schemars::schema_for!(ReviewOutcome)).unwrap();
+        for field in ["critique", "revised_prompt", "deficits", "severity", "quote", "fix", "score"] {
+            assert!(schema.contains(field), "missing {field}");
+        }
+    }
 }
\ No newline at end of file
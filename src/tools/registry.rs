@@ -0,0 +1,224 @@
+use std::fmt;
+use std::sync::Arc;
+
+use rig::completion::ToolDefinition;
+use rig::tool::server::ToolServerHandle;
+use rig::tool::{Tool, ToolDyn, ToolError, ToolSet, ToolSetError};
+use rig::wasm_compat::WasmBoxedFuture;
+
+use crate::error::{Result, ScribeError};
+
+/// The tools the Prompt Officer gets on top of, or instead of, the built-in ones.
+///
+/// Register your own [`Tool`]s (or a whole [`ToolSet`]) and remove built-in tools by
+/// name. A registered tool with the same name as a built-in one replaces it.
+///
+/// # Examples
+///
+/// ```
+/// use rig::{completion::ToolDefinition, tool::Tool};
+/// use rigscribe::{ScribeError, tools::registry::ToolRegistry};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct GlossaryArgs {
+///     term: String,
+/// }
+///
+/// struct Glossary;
+///
+/// impl Tool for Glossary {
+///     const NAME: &'static str = "Glossary";
+///     type Error = ScribeError;
+///     type Args = GlossaryArgs;
+///     type Output = String;
+///
+///     async fn definition(&self, _prompt: String) -> ToolDefinition {
+///         ToolDefinition {
+///             name: Self::NAME.to_string(),
+///             description: "Looks up a product term.".to_string(),
+///             parameters: serde_json::json!({
+///                 "type": "object",
+///                 "properties": { "term": { "type": "string" } }
+///             }),
+///         }
+///     }
+///
+///     async fn call(&self, args: Self::Args) -> Result<String, ScribeError> {
+///         Ok(format!("{}: see the product glossary.", args.term))
+///     }
+/// }
+///
+/// let tools = ToolRegistry::default().with_tool(Glossary).without("WebSearcher");
+/// assert!(!tools.is_enabled("WebSearcher"));
+/// assert!(tools.is_enabled("Deconstructor"));
+/// ```
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn ToolDyn>>,
+    toolsets: Vec<Arc<ToolSet>>,
+    removed: Vec<String>,
+}
+
+impl ToolRegistry {
+    /// Adds `tool` to the Prompt Officer's tools.
+    pub fn with_tool(mut self, tool: impl Tool + 'static) -> Self {
+        self.tools.push(Arc::new(tool));
+        self
+    }
+
+    /// Adds every tool in `toolset` to the Prompt Officer's tools.
+    pub fn with_toolset(mut self, toolset: ToolSet) -> Self {
+        self.toolsets.push(Arc::new(toolset));
+        self
+    }
+
+    /// Removes the built-in tool called `name` (e.g. `"WebSearcher"`).
+    pub fn without(mut self, name: impl Into<String>) -> Self {
+        self.removed.push(name.into());
+        self
+    }
+
+    /// Whether the built-in tool called `name` is still offered.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.removed.iter().any(|removed| removed == name)
+    }
+
+    /// Registers the enabled `builtins` and every custom tool with an agent's tool server.
+    ///
+    /// Returns the names of all registered tools, built-in ones first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Config`] if the tool server rejects a tool.
+    pub(crate) async fn install(
+        &self,
+        handle: &ToolServerHandle,
+        builtins: Vec<Arc<dyn ToolDyn>>,
+    ) -> Result<Vec<String>> {
+        let mut custom: Vec<Arc<dyn ToolDyn>> = self.tools.clone();
+        for toolset in &self.toolsets {
+            let definitions = toolset.get_tool_definitions().await.map_err(|e| {
+                ScribeError::Config(format!("Failed to list the tools of a custom toolset: {e}"))
+            })?;
+            custom.extend(definitions.into_iter().map(|definition| {
+                Arc::new(ToolSetMember {
+                    toolset: toolset.clone(),
+                    definition,
+                }) as Arc<dyn ToolDyn>
+            }));
+        }
+        let custom_names: Vec<String> = custom.iter().map(|tool| tool.name()).collect();
+
+        let mut names = Vec::new();
+        let builtins = builtins.into_iter().filter(|tool| {
+            let name = tool.name();
+            self.is_enabled(&name) && !custom_names.contains(&name)
+        });
+        for tool in builtins.chain(custom) {
+            let name = tool.name();
+            handle
+                .add_tool(SharedTool(tool))
+                .await
+                .map_err(|e| ScribeError::Config(format!("Failed to register tool {name}: {e}")))?;
+            names.push(name);
+        }
+        Ok(names)
+    }
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.iter().map(|tool| tool.name()).collect::<Vec<_>>())
+            .field("toolsets", &self.toolsets.len())
+            .field("removed", &self.removed)
+            .finish()
+    }
+}
+
+/// A tool shared between runs; each run registers its own handle to it.
+struct SharedTool(Arc<dyn ToolDyn>);
+
+impl ToolDyn for SharedTool {
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn definition<'a>(&'a self, prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        self.0.definition(prompt)
+    }
+
+    fn call<'a>(&'a self, args: String) -> WasmBoxedFuture<'a, std::result::Result<String, ToolError>> {
+        self.0.call(args)
+    }
+}
+
+/// One tool of a registered [`ToolSet`], called through the set.
+struct ToolSetMember {
+    toolset: Arc<ToolSet>,
+    definition: ToolDefinition,
+}
+
+impl ToolDyn for ToolSetMember {
+    fn name(&self) -> String {
+        self.definition.name.clone()
+    }
+
+    fn definition<'a>(&'a self, _prompt: String) -> WasmBoxedFuture<'a, ToolDefinition> {
+        Box::pin(async move { self.definition.clone() })
+    }
+
+    fn call<'a>(&'a self, args: String) -> WasmBoxedFuture<'a, std::result::Result<String, ToolError>> {
+        Box::pin(async move {
+            self.toolset
+                .call(&self.definition.name, args)
+                .await
+                .map_err(|e| match e {
+                    ToolSetError::ToolCallError(e) => e,
+                    other => ToolError::ToolCallError(Box::new(other)),
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::submit_prompt::SubmitPrompt;
+    use rig::tool::server::ToolServer;
+
+    #[test]
+    fn test_without_disables_builtin() {
+        let registry = ToolRegistry::default().without("WebSearcher");
+        assert!(!registry.is_enabled("WebSearcher"));
+        assert!(registry.is_enabled("PromptReviewer"));
+    }
+
+    #[tokio::test]
+    async fn test_install_registers_builtins_and_toolsets() {
+        let handle = ToolServer::new().run();
+        let registry = ToolRegistry::default()
+            .with_toolset(ToolSet::from_tools(vec![SubmitPrompt]))
+            .without("Removed");
+
+        let builtins: Vec<Arc<dyn ToolDyn>> = vec![Arc::new(SubmitPrompt)];
+        let names = registry.install(&handle, builtins).await.unwrap();
+        // The toolset's SubmitPrompt replaces the built-in one.
+        assert_eq!(names, vec!["SubmitPrompt"]);
+
+        let definitions = handle.get_tool_defs(None).await.unwrap();
+        assert_eq!(definitions.len(), 1);
+        let result = handle
+            .call_tool("SubmitPrompt", r#"{"system_prompt": "Be brief."}"#)
+            .await
+            .unwrap();
+        assert!(result.contains("Prompt submitted."));
+    }
+
+    #[test]
+    fn test_debug_lists_tool_names() {
+        let registry = ToolRegistry::default().with_tool(SubmitPrompt);
+        assert!(format!("{registry:?}").contains("SubmitPrompt"));
+    }
+}
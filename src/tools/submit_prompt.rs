@@ -0,0 +1,86 @@
+use crate::error::{Result, ScribeError};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Arguments for the `SubmitPrompt` tool.
+#[derive(Deserialize, Debug, Clone, Serialize, JsonSchema)]
+pub struct SubmitPromptArgs {
+    /// The final, optimized system prompt. Nothing else.
+    #[schemars(
+        description = "The final, optimized system prompt, verbatim. Do not include commentary, explanations or surrounding code fences."
+    )]
+    pub system_prompt: String,
+}
+
+/// A tool the Prompt Officer calls to hand over its final system prompt.
+///
+/// The optimizer builds the [`Artifact`](crate::Artifact) from the arguments of this call
+/// instead of from the officer's streamed narration, so commentary such as
+/// "Now I will call the Deconstructor…" never leaks into the result.
+#[derive(Serialize, Deserialize)]
+pub struct SubmitPrompt;
+
+impl Tool for SubmitPrompt {
+    const NAME: &'static str = "SubmitPrompt";
+
+    type Error = ScribeError;
+    type Args = SubmitPromptArgs;
+    type Output = String;
+
+    /// Returns the tool definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rig::tool::Tool;
+    /// use rigscribe::tools::submit_prompt::SubmitPrompt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let def = SubmitPrompt.definition("".into()).await;
+    ///     assert_eq!(def.name, "SubmitPrompt");
+    /// }
+    /// ```
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let schema = schemars::schema_for!(SubmitPromptArgs);
+        let parameters = serde_json::to_value(schema).unwrap();
+        ToolDefinition {
+            name: "SubmitPrompt".to_string(),
+            description: "Submit the final optimized system prompt. Call this exactly once, as your last step, with the system prompt only.".to_string(),
+            parameters,
+        }
+    }
+
+    /// Acknowledges the submission.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if the submitted prompt is empty.
+    async fn call(&self, args: Self::Args) -> Result<Self::Output> {
+        tracing::info!("[Tool Calling]-> SubmitPrompt ({} chars)", args.system_prompt.len());
+        if args.system_prompt.trim().is_empty() {
+            return Err(ScribeError::Validation("Submitted system prompt is empty".into()));
+        }
+        Ok("Prompt submitted.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_submit_prompt_definition() {
+        let def = SubmitPrompt.definition("".into()).await;
+        assert_eq!(def.name, "SubmitPrompt");
+        assert!(def.parameters.to_string().contains("system_prompt"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_prompt_rejects_empty() {
+        let args = SubmitPromptArgs { system_prompt: "  ".into() };
+        assert!(matches!(SubmitPrompt.call(args).await, Err(ScribeError::Validation(_))));
+    }
+}
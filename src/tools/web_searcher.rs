@@ -1,22 +1,94 @@
-use crate::types::Webquery;
+use std::sync::Arc;
+
+use crate::agents::run_log::RunLog;
+use crate::types::{RigScribeConfig, SearchResult, Webquery};
 use crate::error::{Result, ScribeError};
+use crate::search::{CachedProvider, SearchBackend, SearchFailurePolicy, SearchProvider};
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use serde::{Deserialize, Serialize};
-use serpscraper::get_markdown_for_query;
+
+/// The output of the `WebSearcher` tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SearchResults {
+    /// The findings, most relevant first.
+    pub results: Vec<SearchResult>,
+    /// Set when the search failed and the results are empty because of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
 
 /// A tool for performing web searches to gather external information.
 ///
-/// This tool uses the `serpscraper` library (wrapping an API like Serper.dev)
-/// to fetch search results in Markdown format.
-#[derive(Serialize, Deserialize)]
-pub struct WebSearcher;
+/// The actual search is delegated to a [`SearchProvider`], selected through
+/// [`RigScribeConfig::search`]. By default this is Google through SerpApi, including the
+/// content of each result page. Results come back as title/URL/snippet records, with the
+/// page content when the backend fetched it, and, when a [`RunLog`] is attached, are
+/// recorded as the run's sources.
+#[derive(Debug, Clone)]
+pub struct WebSearcher {
+    provider: Arc<dyn SearchProvider>,
+    on_failure: SearchFailurePolicy,
+    log: Option<RunLog>,
+}
+
+impl WebSearcher {
+    /// Creates a `WebSearcher` backed by `provider`. Search failures abort the run.
+    pub fn new(provider: Arc<dyn SearchProvider>) -> Self {
+        Self {
+            provider,
+            on_failure: SearchFailurePolicy::default(),
+            log: None,
+        }
+    }
+
+    /// Records every result handed to the model in `log`, and the search failure that
+    /// aborts the run, if any.
+    pub fn with_log(mut self, log: RunLog) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Sets what happens when the backend fails.
+    pub fn on_failure(mut self, policy: SearchFailurePolicy) -> Self {
+        self.on_failure = policy;
+        self
+    }
+
+    /// Creates a `WebSearcher` using the backend selected in `config`, behind the search
+    /// cache if one is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{RigScribeConfig, search::SearchBackend, tools::web_searcher::WebSearcher};
+    ///
+    /// let config = RigScribeConfig {
+    ///     search: SearchBackend::LocalCorpus { root: "./docs".into() },
+    ///     ..Default::default()
+    /// };
+    /// let tool = WebSearcher::from_config(&config);
+    /// ```
+    pub fn from_config(config: &RigScribeConfig) -> Self {
+        let provider = match &config.search_cache {
+            Some(cache) => Arc::new(CachedProvider::new(config.search.provider(), cache.clone())),
+            None => config.search.provider(),
+        };
+        Self::new(provider).on_failure(config.on_search_failure)
+    }
+}
+
+impl Default for WebSearcher {
+    fn default() -> Self {
+        Self::new(SearchBackend::default().provider())
+    }
+}
 
 impl Tool for WebSearcher {
     const NAME: &'static str = "WebSearcher";
     type Error = ScribeError;
     type Args = Webquery;
-    type Output = String;
+    type Output = SearchResults;
 
     /// Defines the tool interface.
     ///
@@ -28,7 +100,7 @@ impl Tool for WebSearcher {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let tool = WebSearcher;
+    ///     let tool = WebSearcher::default();
     ///     let def = tool.definition("".into()).await;
     ///     assert_eq!(def.name, "WebSearcher");
     /// }
@@ -47,7 +119,9 @@ impl Tool for WebSearcher {
     ///
     /// # Errors
     ///
-    /// Returns [`ScribeError::Config`] if `SERPER_API_KEY` is missing.
+    /// With [`SearchFailurePolicy::Abort`], returns [`ScribeError::Config`] if the backend is
+    /// misconfigured (for example `SERPAPI_API_KEY` is missing) and [`ScribeError::Search`] if
+    /// the search itself fails. With [`SearchFailurePolicy::ReturnEmpty`] this never fails.
     ///
     /// # Examples
     ///
@@ -57,32 +131,121 @@ impl Tool for WebSearcher {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     // Requires SERPER_API_KEY
+    ///     // Requires SERPAPI_API_KEY
     /// }
     /// ```
     async fn call(&self, args: Self::Args) -> Result<Self::Output> {
-        tracing::info!("[Tool Calling]-> WebSearcher with args: {:?}", args);
-        let api_key = std::env::var("SERPER_API_KEY").map_err(
-            |e| 
-                ScribeError::Config(format!("SERPER_API_KEY not set: {}", e))
+        tracing::info!(
+            "[Tool Calling]-> WebSearcher ({}) with args: {:?}",
+            self.provider.name(),
+            args
         );
-        let markdown = get_markdown_for_query(&args.query, &api_key?.to_string()).await;
-        Ok(markdown.unwrap().to_string())
+        match self.provider.search(&args.query).await {
+            Ok(results) => {
+                if let Some(log) = &self.log {
+                    log.record_sources(&results);
+                }
+                Ok(SearchResults { results, warning: None })
+            }
+            Err(e) if self.on_failure == SearchFailurePolicy::ReturnEmpty => {
+                tracing::warn!("Search failed, continuing without results: {}", e);
+                Ok(SearchResults {
+                    results: Vec::new(),
+                    warning: Some(format!(
+                        "The search failed ({e}). Continue without research input and do not claim to have consulted external sources."
+                    )),
+                })
+            }
+            Err(e) => {
+                if let (Some(log), ScribeError::Search { query, cause }) = (&self.log, &e) {
+                    log.record_search_failure(query, cause);
+                }
+                Err(e)
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::future::BoxFuture;
+
+    #[derive(Debug)]
+    struct Stub;
+
+    impl SearchProvider for Stub {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+            Box::pin(async move { Ok(vec![SearchResult::new(query, "https://stub", "stubbed")]) })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Failing;
+
+    impl SearchProvider for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn search<'a>(&'a self, query: &'a str) -> BoxFuture<'a, Result<Vec<SearchResult>>> {
+            Box::pin(async move {
+                Err(ScribeError::Search {
+                    query: query.to_string(),
+                    cause: "quota exceeded".to_string(),
+                })
+            })
+        }
+    }
 
     #[tokio::test]
     async fn test_web_searcher_definition() {
-        let tool = WebSearcher;
+        let tool = WebSearcher::default();
         let def = tool.definition("".into()).await;
         assert_eq!(def.name, "WebSearcher");
         assert!(def.parameters.to_string().contains("query"));
     }
 
-    // TODO (UNTESTABLE): test_web_searcher_call
-    // Requires live API key and internet access. serpscraper does not seem to offer a mock interface here.
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn test_web_searcher_call_uses_provider() {
+        let log = RunLog::default();
+        let tool = WebSearcher::new(Arc::new(Stub)).with_log(log.clone());
+        let output = tool.call(Webquery { query: "rust".into() }).await.unwrap();
+        assert_eq!(output.results[0].title, "rust");
+        assert!(output.warning.is_none());
+        assert_eq!(log.sources().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_web_searcher_failure_aborts_by_default() {
+        let tool = WebSearcher::new(Arc::new(Failing));
+        let result = tool.call(Webquery { query: "rust".into() }).await;
+        match result {
+            Err(ScribeError::Search { query, cause }) => {
+                assert_eq!(query, "rust");
+                assert_eq!(cause, "quota exceeded");
+            }
+            _ => panic!("Expected Search error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_web_searcher_failure_is_recorded() {
+        let log = RunLog::default();
+        let tool = WebSearcher::new(Arc::new(Failing)).with_log(log.clone());
+        assert!(tool.call(Webquery { query: "rust".into() }).await.is_err());
+        assert!(matches!(log.search_failure(), Some(ScribeError::Search { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_web_searcher_failure_returns_empty_with_warning() {
+        let tool = WebSearcher::new(Arc::new(Failing)).on_failure(SearchFailurePolicy::ReturnEmpty);
+        let output = tool.call(Webquery { query: "rust".into() }).await.unwrap();
+        assert!(output.results.is_empty());
+        assert!(output.warning.unwrap().contains("quota exceeded"));
+    }
+}
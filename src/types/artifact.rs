@@ -1,5 +1,12 @@
+use rig::message::Message;
+use crate::error::Result;
+use crate::lint::{LintFinding, Linter};
+use crate::types::template::render;
+use crate::signing::Signature;
+use crate::types::{CritiqueReport, FewShotExample, InjectionAudit, TemplateVariable, SearchResult};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
+use sha2::{Digest, Sha256};
 
 /// Represents the final output of the optimization pipeline.
 ///
@@ -11,6 +18,68 @@ pub struct Artifact {
     pub system_prompt: String,
     /// The name or identifier of the agent that produced this artifact.
     pub signed_by: String,
+    /// The model's reasoning trace for the run that produced this artifact.
+    ///
+    /// Only recorded when [`RigScribeConfig::keep_reasoning`](crate::RigScribeConfig) is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub reasoning: Option<String>,
+    /// The Prompt Officer's conversation for the run that produced this artifact.
+    ///
+    /// Only recorded when [`RigScribeConfig::keep_transcript`](crate::RigScribeConfig) is set.
+    /// It can be passed back to [`multi_turn_prompt`](crate::agents::multi_turn_prompt) to
+    /// continue the conversation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub transcript: Option<Vec<Message>>,
+    /// The search results consulted while producing this artifact.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(skip)]
+    pub sources: Vec<SearchResult>,
+    /// The `PromptReviewer`'s critique of the draft this prompt was rewritten from: what was
+    /// wrong with it and how each problem was fixed. If no rewrite scored higher than the
+    /// original draft, the draft is kept and this is the critique that scored it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub review: Option<CritiqueReport>,
+    /// Scores (0-100) of the revisions the `PromptReviewer` went through, starting with the
+    /// original draft. Has more than one entry only with
+    /// [`RigScribeConfig::review_loop`](crate::RigScribeConfig) set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(skip)]
+    pub score_history: Vec<u8>,
+    /// Worked input/output examples to accompany the prompt. See
+    /// [`Artifact::few_shot_messages`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(skip)]
+    pub examples: Vec<FewShotExample>,
+    /// The prompt-injection checks this prompt still fails. Only recorded when the
+    /// `InjectionAuditor` took part in the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub injection_audit: Option<InjectionAudit>,
+    /// The language the system prompt is written in, if it was requested or translated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub language: Option<String>,
+    /// For a translation, the language of the artifact it was translated from. Variants of
+    /// one scope are cached side by side; see [`RigScribe::translate_with_cache`](crate::RigScribe::translate_with_cache).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub translated_from: Option<String>,
+    /// For a translation, the [`digest`](Artifact::digest) of the artifact it was translated
+    /// from. A cached variant whose source digest no longer matches is stale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub source_digest: Option<String>,
+    /// Proof of who approved this artifact; see [`Artifact::sign`] and [`Artifact::verify`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub signature: Option<Signature>,
+    /// The runtime values the prompt takes as `{{name}}` placeholders; see [`Artifact::render`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(skip)]
+    pub variables: Vec<TemplateVariable>,
 }
 
 impl Artifact {
@@ -34,8 +103,121 @@ impl Artifact {
         Self {
             system_prompt: system_prompt.into(),
             signed_by: signed_by.into(),
+            reasoning: None,
+            transcript: None,
+            sources: Vec::new(),
+            review: None,
+            score_history: Vec::new(),
+            examples: Vec::new(),
+            injection_audit: None,
+            language: None,
+            translated_from: None,
+            source_digest: None,
+            signature: None,
+            variables: Vec::new(),
         }
     }
+
+    /// A hex-encoded SHA-256 digest of what a translation carries over: the system prompt,
+    /// the examples and the variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::Artifact;
+    ///
+    /// let brief = Artifact::new("Be brief.", "");
+    /// assert_eq!(brief.digest(), Artifact::new("Be brief.", "Agent-007").digest());
+    /// assert_ne!(brief.digest(), Artifact::new("Be thorough.", "").digest());
+    /// ```
+    pub fn digest(&self) -> String {
+        let content = (&self.system_prompt, &self.examples, &self.variables);
+        let bytes = serde_json::to_vec(&content).expect("prompt, examples and variables always serialize");
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    /// Renders [`Artifact::examples`] as alternating user/assistant messages, ready to be
+    /// passed as chat history after the system prompt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Artifact, FewShotExample};
+    ///
+    /// let mut artifact = Artifact::new("You convert dates to ISO 8601.", "");
+    /// artifact.examples.push(FewShotExample {
+    ///     input: "5 Jan 2024".into(),
+    ///     output: "2024-01-05".into(),
+    ///     edge_case: false,
+    /// });
+    /// assert_eq!(artifact.few_shot_messages().len(), 2);
+    /// ```
+    pub fn few_shot_messages(&self) -> Vec<Message> {
+        self.examples
+            .iter()
+            .flat_map(|example| {
+                [
+                    Message::user(example.input.as_str()),
+                    Message::assistant(example.output.as_str()),
+                ]
+            })
+            .collect()
+    }
+
+    /// Runs the offline [`lint`](crate::lint::lint) checks over the system prompt.
+    /// Placeholders of declared [`variables`](Artifact::variables) are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Artifact, TemplateVariable};
+    ///
+    /// let mut artifact = Artifact::new("Summarise {{document}}. Respond in Markdown.", "");
+    /// assert_eq!(artifact.lint().len(), 1);
+    ///
+    /// artifact.variables.push(TemplateVariable::new("document", "The text to summarise."));
+    /// assert!(artifact.lint().is_empty());
+    /// ```
+    pub fn lint(&self) -> Vec<LintFinding> {
+        Linter::default().lint_artifact(self)
+    }
+
+    /// Fills the system prompt's `{{name}}` placeholders with `values`, falling back to
+    /// each declared variable's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`](crate::ScribeError::Validation) if a value is
+    /// given for an undeclared variable, if a placeholder has neither a value nor a default,
+    /// or if a value does not match its variable's type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Artifact, TemplateVariable, VariableType};
+    ///
+    /// let mut artifact = Artifact::new("You support {{product}} customers on the {{tier}} plan.", "");
+    /// artifact.variables = vec![
+    ///     TemplateVariable::new("product", "The product name."),
+    ///     TemplateVariable::new("tier", "The customer's plan.").default_value("Free"),
+    /// ];
+    /// let prompt = artifact.render([("product", "Acme")]).unwrap();
+    /// assert_eq!(prompt, "You support Acme customers on the Free plan.");
+    /// assert!(artifact.render([("locale", "fr")]).is_err());
+    /// ```
+    pub fn render<K: Into<String>, V: Into<String>>(
+        &self,
+        values: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<String> {
+        let values = values.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        render(&self.system_prompt, &self.variables, values)
+    }
+
+    /// Audits the system prompt's resistance to prompt injection, offline. See
+    /// [`InjectionAudit::of`].
+    pub fn audit_injection(&self) -> InjectionAudit {
+        InjectionAudit::of(&self.system_prompt)
+    }
 }
 
 #[cfg(test)]
@@ -65,8 +247,47 @@ mod tests {
         
         assert_eq!(artifact.system_prompt, "Deserialize me");
         assert_eq!(artifact.signed_by, "Agent B");
+        assert!(artifact.reasoning.is_none());
+        assert!(artifact.sources.is_empty());
+        assert!(artifact.review.is_none());
+    }
+
+    #[test]
+    fn test_artifact_reasoning_not_serialized_when_absent() {
+        let artifact = Artifact::new("A", "B");
+        let json = serde_json::to_string(&artifact).unwrap();
+        assert!(!json.contains("reasoning"));
+    }
+
+    #[test]
+    fn test_artifact_transcript_roundtrip() {
+        let mut artifact = Artifact::new("A", "B");
+        artifact.transcript = Some(vec![Message::user("hi"), Message::assistant("hello")]);
+        let json = serde_json::to_string(&artifact).unwrap();
+        let loaded: Artifact = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.transcript.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_artifact_schema_excludes_reasoning() {
+        let schema = schemars::schema_for!(Artifact);
+        let json = serde_json::to_string(&schema).unwrap();
+        assert!(!json.contains("reasoning"));
     }
     
+    #[test]
+    fn test_few_shot_messages_alternate_roles() {
+        let mut artifact = Artifact::new("A", "B");
+        artifact.examples = vec![
+            FewShotExample { input: "in 1".into(), output: "out 1".into(), edge_case: false },
+            FewShotExample { input: "in 2".into(), output: "out 2".into(), edge_case: true },
+        ];
+        let messages = artifact.few_shot_messages();
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[0], Message::User { .. }));
+        assert!(matches!(messages[3], Message::Assistant { .. }));
+    }
+
     #[test]
     fn test_artifact_clone() {
          let artifact = Artifact::new("A", "B");
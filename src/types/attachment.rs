@@ -0,0 +1,156 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::FewShotExample;
+
+/// A document the prompt has to work with, e.g. a style guide or a product FAQ.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ReferenceDocument {
+    /// What the document is.
+    #[schemars(description = "A short title for the document, e.g. 'Refund policy'.")]
+    pub title: String,
+    /// The document's text.
+    #[schemars(description = "The document's text, verbatim.")]
+    pub content: String,
+}
+
+impl ReferenceDocument {
+    /// Creates a document from its title and text.
+    pub fn new(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Material the requester attaches to an [`Intent`](crate::Intent) so that the prompt is
+/// written for real data instead of guesses.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{Attachments, FewShotExample, Intent, ReferenceDocument};
+///
+/// let attachments = Attachments {
+///     examples: vec![FewShotExample {
+///         input: "Where is my order #1042?".into(),
+///         output: "It shipped yesterday; here is the tracking link: ...".into(),
+///         edge_case: false,
+///     }],
+///     documents: vec![ReferenceDocument::new("Refund policy", "Refunds within 30 days.")],
+///     target_model: Some("gpt-4o-mini".into()),
+///     audience: Some("Non-technical shoppers".into()),
+///     requirements: vec!["Never promise a delivery date".into()],
+/// };
+/// let intent = Intent::new("Answer support tickets").unwrap().with_attachments(attachments);
+/// assert!(intent.full_text().contains("Never promise a delivery date"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct Attachments {
+    /// Sample inputs the prompt will receive, with the output expected for each.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Real sample inputs with the output expected for each.")]
+    pub examples: Vec<FewShotExample>,
+    /// Documents the prompt has to work with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Reference documents the prompt must be consistent with.")]
+    pub documents: Vec<ReferenceDocument>,
+    /// The model the prompt will be deployed on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The model the prompt will run on, e.g. 'gpt-4o-mini'. Optional.")]
+    pub target_model: Option<String>,
+    /// Who the prompted model talks to or writes for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Who the prompted model's output is for. Optional.")]
+    pub audience: Option<String>,
+    /// Requirements the prompt must meet, verbatim.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Hard requirements the prompt must meet, one per item, verbatim.")]
+    pub requirements: Vec<String>,
+}
+
+impl Attachments {
+    /// Whether nothing is attached.
+    pub fn is_empty(&self) -> bool {
+        self.examples.is_empty()
+            && self.documents.is_empty()
+            && self.target_model.is_none()
+            && self.audience.is_none()
+            && self.requirements.is_empty()
+    }
+
+    /// Renders the attachments as Markdown sections, leaving out what is not attached.
+    /// Examples and documents are wrapped in tags so that their own Markdown stays apart.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for (title, text) in [("Target model", &self.target_model), ("Audience", &self.audience)] {
+            if let Some(text) = text {
+                out.push_str(&format!("\n## {title}\n{text}\n"));
+            }
+        }
+        if !self.requirements.is_empty() {
+            out.push_str("\n## Hard requirements (must all be met)\n");
+            for requirement in &self.requirements {
+                out.push_str(&format!("- {requirement}\n"));
+            }
+        }
+        if !self.examples.is_empty() {
+            out.push_str("\n## Sample inputs and expected outputs\n");
+            for (n, example) in self.examples.iter().enumerate() {
+                let kind = if example.edge_case { " edge_case=\"true\"" } else { "" };
+                out.push_str(&format!(
+                    "<sample n=\"{}\"{kind}>\n<input>\n{}\n</input>\n<output>\n{}\n</output>\n</sample>\n",
+                    n + 1,
+                    example.input,
+                    example.output
+                ));
+            }
+        }
+        if !self.documents.is_empty() {
+            out.push_str("\n## Reference documents\n");
+            for document in &self.documents {
+                out.push_str(&format!(
+                    "<document title=\"{}\">\n{}\n</document>\n",
+                    document.title.replace('"', "'"),
+                    document.content
+                ));
+            }
+        }
+        out.trim_start().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_attachments_render_nothing() {
+        let attachments: Attachments = serde_json::from_str("{}").unwrap();
+        assert!(attachments.is_empty());
+        assert_eq!(attachments.to_markdown(), "");
+        assert_eq!(serde_json::to_string(&attachments).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let attachments = Attachments {
+            examples: vec![FewShotExample {
+                input: "# Title".into(),
+                output: "ok".into(),
+                edge_case: true,
+            }],
+            documents: vec![ReferenceDocument::new("The \"FAQ\"", "Q: A?")],
+            target_model: Some("llama-3-8b".into()),
+            requirements: vec!["Answer in English".into()],
+            ..Default::default()
+        };
+        let markdown = attachments.to_markdown();
+        assert!(markdown.starts_with("## Target model\nllama-3-8b\n"));
+        assert!(!markdown.contains("## Audience"));
+        assert!(markdown.contains("- Answer in English\n"));
+        assert!(markdown.contains("<sample n=\"1\" edge_case=\"true\">\n<input>\n# Title\n</input>"));
+        assert!(markdown.contains("<document title=\"The 'FAQ'\">\nQ: A?\n</document>"));
+    }
+}
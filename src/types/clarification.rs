@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ScribeError};
+use crate::types::{Artifact, Clarification, Intent, Specification};
+
+/// Questions to put to the requester before an [`Intent`] can be optimized.
+///
+/// Returned in [`Outcome::NeedsClarification`] when the `Deconstructor` finds the intent
+/// under-specified. Answer it with [`ClarificationRequest::answer`] and optimize the
+/// resulting intent.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClarificationRequest {
+    /// The intent as it was submitted.
+    pub intent: Intent,
+    /// The specification the `Deconstructor` derived from it.
+    pub spec: Specification,
+    /// The questions to answer, in order.
+    pub questions: Vec<String>,
+}
+
+impl ClarificationRequest {
+    /// Merges `answers`, one per question and in the same order, into the intent.
+    ///
+    /// Blank answers are recorded as leaving the decision to the agents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScribeError::Validation`] if the number of answers does not match the
+    /// number of questions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{ClarificationRequest, Intent, Specification};
+    ///
+    /// let request = ClarificationRequest {
+    ///     intent: Intent::new("write a python function").unwrap(),
+    ///     spec: Specification::default(),
+    ///     questions: vec!["What should the function do?".into()],
+    /// };
+    /// let intent = request.answer(vec!["Parse ISO 8601 dates".into()]).unwrap();
+    /// assert_eq!(intent.clarifications[0].answer, "Parse ISO 8601 dates");
+    /// ```
+    pub fn answer(self, answers: Vec<String>) -> Result<Intent> {
+        if answers.len() != self.questions.len() {
+            return Err(ScribeError::Validation(format!(
+                "Expected {} answers to the clarifying questions, got {}",
+                self.questions.len(),
+                answers.len()
+            )));
+        }
+        let mut intent = self.intent;
+        intent
+            .clarifications
+            .extend(self.questions.into_iter().zip(answers).map(|(question, answer)| {
+                let answer = match answer.trim() {
+                    "" => "No preference; use your best judgement.".to_string(),
+                    answer => answer.to_string(),
+                };
+                Clarification { question, answer }
+            }));
+        Ok(intent)
+    }
+}
+
+/// The result of [`RigScribe::optimize_or_clarify`](crate::RigScribe::optimize_or_clarify).
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The intent was clear enough and has been optimized.
+    Ready(Artifact),
+    /// The intent is under-specified; the requester has to answer these questions first.
+    NeedsClarification(ClarificationRequest),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ClarificationRequest {
+        ClarificationRequest {
+            intent: Intent::new("write a python fonction").unwrap(),
+            spec: Specification::default(),
+            questions: vec!["Purpose?".into(), "Inputs?".into()],
+        }
+    }
+
+    #[test]
+    fn test_answer_merges_into_intent() {
+        let intent = request().answer(vec!["Sort a list".into(), " ".into()]).unwrap();
+        assert_eq!(intent.text, "write a python fonction");
+        assert_eq!(intent.clarifications.len(), 2);
+        assert_eq!(intent.clarifications[0].question, "Purpose?");
+        assert!(intent.clarifications[1].answer.contains("No preference"));
+    }
+
+    #[test]
+    fn test_answer_count_mismatch() {
+        match request().answer(vec!["only one".into()]) {
+            Err(ScribeError::Validation(msg)) => assert!(msg.contains("Expected 2 answers")),
+            _ => panic!("Expected Validation error"),
+        }
+    }
+}
@@ -1,3 +1,12 @@
+use std::sync::Arc;
+
+use crate::agents::budget::ContextBudget;
+use crate::observer::{SilentObserver, StreamObserver};
+use crate::signing::SigningKey;
+use crate::search::{SearchBackend, SearchCache, SearchFailurePolicy};
+use crate::tools::registry::ToolRegistry;
+use crate::types::ReviewLoop;
+
 //pub const MODEL: &str = "gemini-3-pro-preview"; // does not work
 //pub const MODEL: &str = "gemini-3-flash-preview"; // does not work
 //pub const MODEL: &str = "gemini-2.5-flash-lite"; //does not work
@@ -14,6 +23,38 @@ pub const MODEL: &str = "gemini-2.5-pro"; // it works
 pub struct RigScribeConfig {
     /// The name of the LLM model to use (e.g., "gemini-1.5-pro").
     pub model: &'static str,
+    /// Whether the model's reasoning trace is kept on the resulting [`Artifact`](crate::Artifact).
+    ///
+    /// Reasoning is never mixed into the system prompt; this only controls whether it is
+    /// recorded alongside it.
+    pub keep_reasoning: bool,
+    /// Whether the Prompt Officer's full conversation (including tool calls and results)
+    /// is kept on the resulting [`Artifact`](crate::Artifact).
+    pub keep_transcript: bool,
+    /// Receives everything the agents stream. Defaults to [`SilentObserver`], so the
+    /// library never writes to stdout on its own.
+    pub observer: Arc<dyn StreamObserver>,
+    /// The backend used by the `WebSearcher` tool for research.
+    pub search: SearchBackend,
+    /// Whether a failed search aborts the run or comes back to the model as an empty result.
+    pub on_search_failure: SearchFailurePolicy,
+    /// When set, search results are cached on disk and repeated queries are answered from
+    /// the cache until they expire. Off by default.
+    pub search_cache: Option<SearchCache>,
+    /// Size limit for research tool results fed back to an agent. Off by default, in which
+    /// case tool results reach the agents unchanged. Results that carry the prompt are not
+    /// cut; see [`ContextBudget::tools`].
+    pub context_budget: Option<ContextBudget>,
+    /// When set, the `PromptReviewer` keeps reviewing its own rewrites until they are good
+    /// enough. Off by default, which is a single review pass.
+    pub review_loop: Option<ReviewLoop>,
+    /// Custom tools for the Prompt Officer, and built-in tools it should not get.
+    pub tools: ToolRegistry,
+    /// The language the generated system prompt is written in, e.g. `"French"` or `"ja"`.
+    /// `None` (the default) leaves it to the Prompt Officer, which usually writes English.
+    pub language: Option<String>,
+    /// When set, every artifact the pipeline produces is signed with this key. Off by default.
+    pub signing_key: Option<SigningKey>,
 }
 
 impl RigScribeConfig {
@@ -36,7 +77,20 @@ impl RigScribeConfig {
 
 impl Default for RigScribeConfig {
     fn default() -> Self {
-        Self { model: MODEL }
+        Self {
+            model: MODEL,
+            keep_reasoning: false,
+            keep_transcript: false,
+            observer: Arc::new(SilentObserver),
+            search: SearchBackend::default(),
+            on_search_failure: SearchFailurePolicy::default(),
+            search_cache: None,
+            context_budget: None,
+            review_loop: None,
+            tools: ToolRegistry::default(),
+            language: None,
+            signing_key: None,
+        }
     }
 }
 
@@ -49,6 +103,13 @@ mod tests {
         let config = RigScribeConfig::default();
         assert_eq!(config.model, MODEL);
         assert_eq!(config.model, "gemini-2.5-pro");
+        assert!(!config.keep_reasoning);
+        assert!(!config.keep_transcript);
+        assert!(config.search_cache.is_none());
+        assert!(config.context_budget.is_none());
+        assert!(config.review_loop.is_none());
+        assert!(config.language.is_none());
+        assert!(config.signing_key.is_none());
     }
 
     #[test]
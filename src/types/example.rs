@@ -0,0 +1,34 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A worked input/output pair showing how a prompted model should behave.
+///
+/// Stored on the [`Artifact`](crate::Artifact) next to the system prompt and rendered
+/// into few-shot messages with [`Artifact::few_shot_messages`](crate::Artifact::few_shot_messages).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct FewShotExample {
+    /// The user message.
+    #[schemars(description = "A realistic user message, verbatim.")]
+    pub input: String,
+    /// The ideal answer to it.
+    #[schemars(
+        description = "The ideal answer, exactly as the model should write it, following every constraint of the specification."
+    )]
+    pub output: String,
+    /// Whether the example covers an edge case rather than a typical request.
+    #[serde(default)]
+    #[schemars(description = "True if the example covers an edge case or a request that must be refused.")]
+    pub edge_case: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_case_defaults_to_false() {
+        let example: FewShotExample =
+            serde_json::from_str(r#"{"input": "2024-01-05", "output": "Friday"}"#).unwrap();
+        assert!(!example.edge_case);
+    }
+}
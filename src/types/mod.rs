@@ -1,9 +1,23 @@
 pub mod config;
 pub mod pipeline;
 pub mod artifact;
+pub mod attachment;
+pub mod audit;
+pub mod clarification;
+pub mod example;
 pub mod common;
+pub mod review;
+pub mod search;
+pub mod template;
 
-pub use config::MODEL;
-pub use pipeline::{Intent, Specification, Webquery};
+pub use config::RigScribeConfig;
+pub use pipeline::{Clarification, Intent, Specification, Webquery};
 pub use artifact::Artifact;
+pub use attachment::{Attachments, ReferenceDocument};
+pub use audit::{InjectionAudit, InjectionCheck, InjectionGap};
+pub use clarification::{ClarificationRequest, Outcome};
+pub use example::FewShotExample;
 pub use common::ScopeId;
+pub use review::{CritiqueReport, Deficit, DeficitCategory, ReviewLoop, Severity};
+pub use search::SearchResult;
+pub use template::{TemplateVariable, VariableType};
@@ -1,4 +1,5 @@
 use crate::error::{Result, ScribeError};
+use crate::types::{Attachments, TemplateVariable};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +13,36 @@ pub struct Intent {
         description = "he raw user intent. You must analyze this to extract technical constraints."
     )]
     pub text: String,
+
+    /// Answers the requester gave to clarifying questions about `text`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(
+        description = "Answers the requester gave to clarifying questions. They take precedence over assumptions."
+    )]
+    pub clarifications: Vec<Clarification>,
+
+    /// Runtime values the prompt must leave as `{{name}}` placeholders.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(
+        description = "Template variables the prompt must contain as {{name}} placeholders, left intact so they can be filled in at runtime."
+    )]
+    pub variables: Vec<TemplateVariable>,
+
+    /// Samples, documents and requirements the prompt has to fit.
+    #[serde(default, skip_serializing_if = "Attachments::is_empty")]
+    #[schemars(
+        description = "Material the requester attached: sample inputs/outputs, reference documents, target model, audience and hard requirements. Treat it as ground truth."
+    )]
+    pub attachments: Attachments,
+}
+
+/// A clarifying question about an [`Intent`] together with the requester's answer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct Clarification {
+    /// The question asked.
+    pub question: String,
+    /// The requester's answer.
+    pub answer: String,
 }
 
 impl Intent {
@@ -37,14 +68,106 @@ impl Intent {
         if text.trim().is_empty() {
             return Err(ScribeError::Validation("Request is empty".into()));
         }
-        Ok(Self { text })
+        Ok(Self {
+            text,
+            clarifications: Vec::new(),
+            variables: Vec::new(),
+            attachments: Attachments::default(),
+        })
+    }
+
+    /// Declares the template variables the prompt must keep as placeholders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Intent, TemplateVariable};
+    ///
+    /// let intent = Intent::new("Answer support questions")
+    ///     .unwrap()
+    ///     .with_variables(vec![TemplateVariable::new("product", "The product name.")]);
+    /// assert!(intent.full_text().contains("{{product}}"));
+    /// ```
+    pub fn with_variables(mut self, variables: Vec<TemplateVariable>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Attaches sample data, documents and requirements the prompt has to fit. See
+    /// [`Attachments`].
+    pub fn with_attachments(mut self, attachments: Attachments) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// Returns the request text followed by the clarifications, variables and attachments,
+    /// if any, in the form the agents are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::{Clarification, Intent};
+    ///
+    /// let mut intent = Intent::new("write a python function").unwrap();
+    /// assert_eq!(intent.full_text(), "write a python function");
+    ///
+    /// intent.clarifications.push(Clarification {
+    ///     question: "What should it do?".into(),
+    ///     answer: "Parse ISO dates".into(),
+    /// });
+    /// assert!(intent.full_text().contains("A: Parse ISO dates"));
+    /// ```
+    pub fn full_text(&self) -> String {
+        let mut text = self.text.clone();
+        if !self.clarifications.is_empty() {
+            text.push_str("\n\nClarifications from the requester:");
+            for clarification in &self.clarifications {
+                text.push_str(&format!(
+                    "\n- Q: {}\n  A: {}",
+                    clarification.question, clarification.answer
+                ));
+            }
+        }
+        if !self.variables.is_empty() {
+            text.push_str("\n\nTemplate variables (keep each placeholder exactly as written; it is filled in at runtime):");
+            for variable in &self.variables {
+                text.push_str(&format!(
+                    "\n- {} ({:?}): {}",
+                    variable.placeholder(),
+                    variable.kind,
+                    variable.description
+                ));
+                if let Some(default) = &variable.default {
+                    text.push_str(&format!(" Default: {default}."));
+                }
+            }
+        }
+        if !self.attachments.is_empty() {
+            text.push_str("\n\nAttached by the requester (real data; fit the prompt to it):\n");
+            text.push_str(&self.attachments.to_markdown());
+        }
+        text
     }
 }
 
 /// A structured technical specification derived from the user's `Intent`.
 ///
-/// This is produced by the `Deconstructor` tool.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// This is produced by the `Deconstructor` tool. Every field except `goal` may be empty
+/// when the request says nothing about it; all of them default when missing, so older
+/// serialized specs (which only had `goal` and a bulleted `constraints` string) still load.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::Specification;
+///
+/// // A spec serialized before the fields were typed.
+/// let json = r#"{"goal": "Write a CLI", "constraints": "- Rust only\n- No network"}"#;
+/// let spec: Specification = serde_json::from_str(json).unwrap();
+/// assert_eq!(spec.constraints, vec!["Rust only", "No network"]);
+/// assert!(spec.risks.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Specification {
     /// The primary goal derived from the user's intent. concise and clear.
     #[schemars(
@@ -52,13 +175,151 @@ pub struct Specification {
     )]
     pub goal: String,
 
-    /// A list of technical constraints, risks, and negative constraints.
+    /// Who the output of the prompted model is for.
+    #[serde(default)]
+    #[schemars(
+        description = "Who will read or use the output, e.g. 'junior Python developers'. Empty if not stated or implied."
+    )]
+    pub target_audience: String,
+
+    /// What the prompted model will be given to work on.
+    #[serde(default)]
+    #[schemars(
+        description = "The inputs the model will receive at run time, one per item (e.g. 'a CSV file of orders')."
+    )]
+    pub inputs: Vec<String>,
+
+    /// The shape of the expected output.
+    #[serde(default)]
+    #[schemars(
+        description = "The expected output format, e.g. 'a single Python file with docstrings' or 'JSON with keys a, b'."
+    )]
+    pub output_format: String,
+
+    /// Hard technical constraints, one per item.
     ///
-    /// Format as a bulleted string.
+    /// Also accepts the legacy bulleted string, which is split into items.
+    #[serde(default, deserialize_with = "string_or_list")]
+    #[schemars(
+        description = "Hard technical constraints the output must satisfy, one per item."
+    )]
+    pub constraints: Vec<String>,
+
+    /// Things the output must not do.
+    #[serde(default)]
+    #[schemars(description = "Things the output must NOT do or contain, one per item.")]
+    pub negative_constraints: Vec<String>,
+
+    /// What could go wrong, one per item.
+    #[serde(default)]
+    #[schemars(description = "Risks and likely failure modes of the task, one per item.")]
+    pub risks: Vec<String>,
+
+    /// What was assumed where the request was silent.
+    #[serde(default)]
+    #[schemars(
+        description = "Assumptions made where the request is silent or ambiguous, one per item."
+    )]
+    pub assumptions: Vec<String>,
+
+    /// How to tell the output is good.
+    #[serde(default)]
     #[schemars(
-        description = "A list of technical constraints, risks, and negative constraints. Format as a bulleted string."
+        description = "Checkable criteria that a good output meets, one per item."
     )]
-    pub constraints: String,
+    pub success_criteria: Vec<String>,
+
+    /// Questions only the requester can answer.
+    #[serde(default)]
+    #[schemars(
+        description = "Questions that only the requester can answer and that materially change the result. Empty if none."
+    )]
+    pub open_questions: Vec<String>,
+}
+
+impl Specification {
+    /// Renders the specification as Markdown, one section per non-empty field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::Specification;
+    ///
+    /// let spec = Specification {
+    ///     goal: "Write a CLI".into(),
+    ///     constraints: vec!["Rust only".into()],
+    ///     ..Default::default()
+    /// };
+    /// let markdown = spec.to_markdown();
+    /// assert!(markdown.contains("## Constraints\n- Rust only"));
+    /// assert!(!markdown.contains("## Risks"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("## Goal\n{}\n", self.goal);
+        for (title, text) in [
+            ("Target audience", &self.target_audience),
+            ("Output format", &self.output_format),
+        ] {
+            if !text.trim().is_empty() {
+                out.push_str(&format!("\n## {title}\n{text}\n"));
+            }
+        }
+        for (title, items) in [
+            ("Inputs", &self.inputs),
+            ("Constraints", &self.constraints),
+            ("Negative constraints", &self.negative_constraints),
+            ("Risks", &self.risks),
+            ("Assumptions", &self.assumptions),
+            ("Success criteria", &self.success_criteria),
+            ("Open questions", &self.open_questions),
+        ] {
+            if !items.is_empty() {
+                out.push_str(&format!("\n## {title}\n"));
+                for item in items {
+                    out.push_str(&format!("- {item}\n"));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Deserializes a list of strings, also accepting a single bulleted string.
+fn string_or_list<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match StringOrList::deserialize(deserializer)? {
+        StringOrList::One(text) => split_bullets(&text),
+        StringOrList::Many(items) => items,
+    })
+}
+
+/// Splits a bulleted or numbered list into its items, without the markers.
+fn split_bullets(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = ["- ", "* ", "• "]
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+                .unwrap_or(line);
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            let line = match line[digits..].strip_prefix(['.', ')']) {
+                Some(rest) if digits > 0 => rest,
+                _ => line,
+            };
+            line.trim().to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
 }
 
 /// A search query intended for a web search tool.
@@ -95,14 +356,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_intent_without_clarifications_loads() {
+        let intent: Intent = serde_json::from_str(r#"{"text": "old"}"#).unwrap();
+        assert!(intent.clarifications.is_empty());
+        assert!(intent.attachments.is_empty());
+        assert_eq!(serde_json::to_string(&intent).unwrap(), r#"{"text":"old"}"#);
+    }
+
     #[test]
     fn test_specification_serialization() {
         let spec = Specification {
             goal: "Goal".into(),
-            constraints: "None".into(),
+            constraints: vec!["None".into()],
+            ..Default::default()
         };
         let json = serde_json::to_string(&spec).unwrap();
         assert!(json.contains("Goal"));
+        let loaded: Specification = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, spec);
+    }
+
+    #[test]
+    fn test_specification_loads_legacy_constraints() {
+        let json = r#"{"goal": "G", "constraints": "* Fast\n2. Safe\n\n- No unsafe code"}"#;
+        let spec: Specification = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.constraints, vec!["Fast", "Safe", "No unsafe code"]);
+        assert!(spec.open_questions.is_empty());
+    }
+
+    #[test]
+    fn test_specification_schema_lists_typed_fields() {
+        let schema = serde_json::to_string(&schemars::schema_for!(Specification)).unwrap();
+        for field in ["target_audience", "negative_constraints", "success_criteria", "open_questions"] {
+            assert!(schema.contains(field), "missing {field}");
+        }
     }
 
     #[test]
@@ -0,0 +1,273 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What aspect of a prompt a [`Deficit`] concerns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeficitCategory {
+    /// Harmful output, jailbreaks or missing guardrails.
+    Safety,
+    /// Vague, ambiguous or unmeasurable instructions.
+    Clarity,
+    /// Missing, conflicting or unenforceable rules.
+    Constraints,
+    /// The expected output format is missing or underspecified.
+    OutputFormat,
+    /// Missing persona, workflow, sections or delimiters.
+    Structure,
+    /// Missing or misleading examples.
+    Examples,
+    /// Anything else.
+    Other,
+}
+
+/// How much a [`Deficit`] matters. Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Cosmetic; the prompt works without the fix.
+    Low,
+    /// Degrades output quality in some cases.
+    Medium,
+    /// Likely to produce wrong or unusable output.
+    High,
+    /// Unsafe, or makes the prompt fail its goal.
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single problem the `PromptReviewer` found in a draft.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct Deficit {
+    /// What aspect of the prompt is affected.
+    #[schemars(description = "What aspect of the prompt is affected.")]
+    pub category: DeficitCategory,
+    /// How much it matters.
+    #[schemars(description = "How much the deficit matters.")]
+    pub severity: Severity,
+    /// The offending text, quoted verbatim from the draft.
+    #[schemars(
+        description = "The offending text quoted verbatim from the draft. Empty if the deficit is something missing."
+    )]
+    pub quote: String,
+    /// What the rewrite does about it.
+    #[schemars(description = "The concrete change that fixes the deficit.")]
+    pub fix: String,
+}
+
+/// The `PromptReviewer`'s assessment of a draft prompt.
+///
+/// Kept on the [`Artifact`](crate::Artifact) as [`Artifact::review`](crate::Artifact::review)
+/// so prompt owners can see why a prompt was changed.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{CritiqueReport, Deficit, DeficitCategory, Severity};
+///
+/// let report = CritiqueReport {
+///     score: 40,
+///     summary: "Vague and unsafe.".into(),
+///     deficits: vec![Deficit {
+///         category: DeficitCategory::Clarity,
+///         severity: Severity::High,
+///         quote: "write a python fonction".into(),
+///         fix: "State the function's purpose, inputs and outputs.".into(),
+///     }],
+/// };
+/// assert_eq!(report.worst_severity(), Some(Severity::High));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct CritiqueReport {
+    /// Overall quality of the draft, from 0 (unusable) to 100 (flawless).
+    #[schemars(
+        description = "Overall quality of the draft BEFORE the rewrite, from 0 (unusable) to 100 (flawless)."
+    )]
+    pub score: u8,
+    /// A one or two sentence verdict.
+    #[schemars(description = "A one or two sentence verdict on the draft.")]
+    pub summary: String,
+    /// Every deficit found, most severe first.
+    #[schemars(description = "Every deficit found in the draft, most severe first.")]
+    pub deficits: Vec<Deficit>,
+}
+
+impl CritiqueReport {
+    /// Returns the severity of the worst deficit, if any were found.
+    pub fn worst_severity(&self) -> Option<Severity> {
+        self.deficits.iter().map(|deficit| deficit.severity).max()
+    }
+
+    /// Caps the score at 100 and orders the deficits most severe first.
+    pub(crate) fn normalized(mut self) -> Self {
+        self.score = self.score.min(100);
+        self.deficits.sort_by_key(|deficit| std::cmp::Reverse(deficit.severity));
+        self
+    }
+}
+
+/// Settings for reviewing a prompt repeatedly until it is good enough.
+///
+/// Each round, the `PromptReviewer` scores the current revision and rewrites it. The loop
+/// stops once a revision scores at least `threshold`, once a revision improves on the
+/// previous one by less than `min_improvement`, or after `max_iterations` rounds, and the
+/// best-scoring revision is kept.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{RigScribeConfig, ReviewLoop};
+///
+/// let config = RigScribeConfig {
+///     review_loop: Some(ReviewLoop { threshold: 90, ..Default::default() }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReviewLoop {
+    /// Score (0-100) at which a revision is accepted.
+    pub threshold: u8,
+    /// Maximum number of review rounds. `1` is the single-pass review.
+    pub max_iterations: usize,
+    /// Smallest score gain over the previous revision that is worth another round.
+    pub min_improvement: u8,
+}
+
+impl Default for ReviewLoop {
+    fn default() -> Self {
+        Self {
+            threshold: 85,
+            max_iterations: 3,
+            min_improvement: 3,
+        }
+    }
+}
+
+impl ReviewLoop {
+    /// Whether to stop after the rounds that produced `scores`, where `scores[k]` is the
+    /// score of revision `k` (revision 0 being the original draft).
+    pub(crate) fn should_stop(&self, scores: &[u8]) -> bool {
+        let Some(&last) = scores.last() else {
+            return false;
+        };
+        if scores.len() >= self.max_iterations.max(1) || last >= self.threshold {
+            return true;
+        }
+        match scores.len().checked_sub(2).map(|i| scores[i]) {
+            Some(previous) => last < previous.saturating_add(self.min_improvement),
+            None => false,
+        }
+    }
+
+    /// Which revision to keep once the loop stopped after the rounds that produced `scores`.
+    ///
+    /// Every round also produced a rewrite, so revisions `0..=scores.len()` exist, the last
+    /// of them unscored. The best-scoring revision is kept, the original draft included, so
+    /// the draft stays when no rewrite beats it (the latest revision wins ties). After a
+    /// single round nothing compares the rewrite to the draft, so the rewrite is kept.
+    pub(crate) fn pick(scores: &[u8]) -> usize {
+        let [first, rest @ ..] = scores else {
+            return 0;
+        };
+        if rest.is_empty() {
+            return 1;
+        }
+        let (best, _) = rest
+            .iter()
+            .enumerate()
+            .fold((0, *first), |(best, max), (k, &score)| {
+                if score >= max { (k + 1, score) } else { (best, max) }
+            });
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deficit(severity: Severity) -> Deficit {
+        Deficit {
+            category: DeficitCategory::Safety,
+            severity,
+            quote: String::new(),
+            fix: "Add guardrails.".into(),
+        }
+    }
+
+    #[test]
+    fn test_normalized_orders_by_severity() {
+        let report = CritiqueReport {
+            score: 250,
+            summary: String::new(),
+            deficits: vec![deficit(Severity::Low), deficit(Severity::Critical)],
+        }
+        .normalized();
+        assert_eq!(report.score, 100);
+        assert_eq!(report.deficits[0].severity, Severity::Critical);
+        assert_eq!(report.worst_severity(), Some(Severity::Critical));
+    }
+
+    #[test]
+    fn test_report_roundtrip_uses_snake_case() {
+        let report = CritiqueReport {
+            score: 70,
+            summary: "ok".into(),
+            deficits: vec![Deficit {
+                category: DeficitCategory::OutputFormat,
+                ..deficit(Severity::Medium)
+            }],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"output_format\""));
+        assert!(json.contains("\"medium\""));
+        let loaded: CritiqueReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, report);
+    }
+
+    #[test]
+    fn test_review_loop_stops_at_threshold() {
+        let review_loop = ReviewLoop { threshold: 80, max_iterations: 5, min_improvement: 0 };
+        assert!(!review_loop.should_stop(&[40]));
+        assert!(!review_loop.should_stop(&[40, 70]));
+        assert!(review_loop.should_stop(&[40, 70, 82]));
+        assert_eq!(ReviewLoop::pick(&[40, 70, 82]), 2);
+    }
+
+    #[test]
+    fn test_review_loop_stops_when_improvement_stalls() {
+        let review_loop = ReviewLoop { threshold: 95, max_iterations: 5, min_improvement: 5 };
+        assert!(review_loop.should_stop(&[40, 70, 72]));
+        // The stalled revision is worse than its predecessor: keep the predecessor.
+        assert!(review_loop.should_stop(&[40, 70, 65]));
+        assert_eq!(ReviewLoop::pick(&[40, 70, 65]), 1);
+    }
+
+    #[test]
+    fn test_review_loop_keeps_the_draft_when_no_rewrite_beats_it() {
+        let review_loop = ReviewLoop { threshold: 95, max_iterations: 5, min_improvement: 5 };
+        assert!(review_loop.should_stop(&[80, 60]));
+        assert_eq!(ReviewLoop::pick(&[80, 60]), 0);
+        assert_eq!(ReviewLoop::pick(&[80, 60, 80]), 2);
+    }
+
+    #[test]
+    fn test_review_loop_max_iterations() {
+        let single = ReviewLoop { max_iterations: 1, ..Default::default() };
+        assert!(single.should_stop(&[10]));
+        assert_eq!(ReviewLoop::pick(&[10]), 1);
+    }
+}
@@ -0,0 +1,56 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A single research finding returned by a search backend.
+///
+/// Search results are kept on the final [`Artifact`](crate::Artifact) as citations, so
+/// reviewers can check which external material shaped a prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct SearchResult {
+    /// Title of the page or document.
+    pub title: String,
+    /// Where the result can be found (`https://…` or `file://…`).
+    pub url: String,
+    /// The relevant excerpt.
+    pub snippet: String,
+    /// The page itself as Markdown, when the backend fetched it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// When the result was fetched, in seconds since the Unix epoch.
+    pub fetched_at: u64,
+}
+
+impl SearchResult {
+    /// Creates a `SearchResult` fetched now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rigscribe::SearchResult;
+    ///
+    /// let result = SearchResult::new("Prompting guide", "https://example.com", "Be specific.");
+    /// assert!(result.fetched_at > 0);
+    /// ```
+    pub fn new(title: impl Into<String>, url: impl Into<String>, snippet: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            url: url.into(),
+            snippet: snippet.into(),
+            content: None,
+            fetched_at: crate::utilities::unix_now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_result_roundtrip() {
+        let result = SearchResult::new("Title", "https://example.com", "Snippet");
+        let json = serde_json::to_string(&result).unwrap();
+        let loaded: SearchResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, result);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ScribeError};
+
+/// The kind of value a [`TemplateVariable`] accepts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableType {
+    /// Any text.
+    #[default]
+    String,
+    /// A whole number, e.g. `3` or `-1`.
+    Integer,
+    /// Any number, e.g. `0.5`.
+    Number,
+    /// `true` or `false`.
+    Boolean,
+}
+
+impl VariableType {
+    /// Whether `value` is a valid value of this type.
+    fn accepts(self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            VariableType::String => true,
+            VariableType::Integer => value.parse::<i64>().is_ok(),
+            VariableType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            VariableType::Boolean => matches!(value, "true" | "false"),
+        }
+    }
+}
+
+/// A runtime value a templated prompt expects, written `{{name}}` in the prompt.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::{TemplateVariable, VariableType};
+///
+/// let tier = TemplateVariable::new("user_tier", "The customer's plan, e.g. Free or Pro.")
+///     .default_value("Free");
+/// let seats = TemplateVariable::new("seats", "Number of seats on the plan.").of_type(VariableType::Integer);
+/// assert_eq!(seats.placeholder(), "{{seats}}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct TemplateVariable {
+    /// The name used in the placeholder.
+    #[schemars(description = "The variable name, as written inside the {{name}} placeholder.")]
+    pub name: String,
+    /// The kind of value it accepts.
+    #[serde(default, rename = "type")]
+    #[schemars(description = "The kind of value: string, integer, number or boolean.")]
+    pub kind: VariableType,
+    /// What the value means.
+    #[serde(default)]
+    #[schemars(description = "What the value means and where it is used.")]
+    pub description: String,
+    /// Used when no value is given at render time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The value used when none is given. Optional.")]
+    pub default: Option<String>,
+}
+
+impl TemplateVariable {
+    /// Creates a required string variable.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: VariableType::String,
+            description: description.into(),
+            default: None,
+        }
+    }
+
+    /// Sets the kind of value the variable accepts.
+    pub fn of_type(mut self, kind: VariableType) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Makes the variable optional with the given default.
+    pub fn default_value(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// The placeholder that stands for the variable in a prompt.
+    pub fn placeholder(&self) -> String {
+        format!("{{{{{}}}}}", self.name)
+    }
+}
+
+/// Every `{{name}}` placeholder in `template`, as (byte range, trimmed name).
+fn placeholders(template: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut rest = 0;
+    while let Some(offset) = template[rest..].find("{{") {
+        let start = rest + offset;
+        let Some(len) = template[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let name = template[start + 2..end - 2].trim();
+        let is_identifier = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+        if is_identifier {
+            found.push((start..end, name));
+            rest = end;
+        } else {
+            rest = start + 2;
+        }
+    }
+    found
+}
+
+/// Fills the `{{name}}` placeholders of `template` with `values`, falling back to each
+/// variable's default.
+///
+/// # Errors
+///
+/// Returns [`ScribeError::Validation`] if a value is given for an undeclared variable, if a
+/// placeholder has no value and no default, or if a value does not match its variable's type.
+pub(crate) fn render(
+    template: &str,
+    variables: &[TemplateVariable],
+    values: HashMap<String, String>,
+) -> Result<String> {
+    let mut unknown: Vec<&str> = values
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !variables.iter().any(|variable| variable.name == *name))
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(ScribeError::Validation(format!(
+            "Unknown template variables: {}",
+            unknown.join(", ")
+        )));
+    }
+
+    let mut resolved = HashMap::new();
+    for variable in variables {
+        if let Some(value) = values.get(&variable.name).or(variable.default.as_ref()) {
+            if !variable.kind.accepts(value) {
+                return Err(ScribeError::Validation(format!(
+                    "Template variable '{}' expects {:?}, got '{}'",
+                    variable.name, variable.kind, value
+                )));
+            }
+            resolved.insert(variable.name.as_str(), value.as_str());
+        }
+    }
+
+    let placeholders = placeholders(template);
+    let mut missing: Vec<&str> = placeholders
+        .iter()
+        .map(|(_, name)| *name)
+        .filter(|name| !resolved.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        return Err(ScribeError::Validation(format!(
+            "Missing values for template variables: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut last = 0;
+    for (range, name) in placeholders {
+        rendered.push_str(&template[last..range.start]);
+        rendered.push_str(resolved[name]);
+        last = range.end;
+    }
+    rendered.push_str(&template[last..]);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn variables() -> Vec<TemplateVariable> {
+        vec![
+            TemplateVariable::new("product", "The product name."),
+            TemplateVariable::new("seats", "Seats").of_type(VariableType::Integer).default_value("1"),
+        ]
+    }
+
+    #[test]
+    fn test_render_uses_values_and_defaults() {
+        let template = "Support {{product}} ({{ seats }} seats). Keep JSON like {\"a\": 1}.";
+        let rendered = render(template, &variables(), values(&[("product", "Acme")])).unwrap();
+        assert_eq!(rendered, "Support Acme (1 seats). Keep JSON like {\"a\": 1}.");
+    }
+
+    #[test]
+    fn test_render_rejects_unknown_missing_and_mistyped() {
+        let template = "Support {{product}} for {{tier}}.";
+        let err = |vals: &[(&str, &str)]| match render(template, &variables(), values(vals)) {
+            Err(ScribeError::Validation(msg)) => msg,
+            other => panic!("Expected Validation error, got {other:?}"),
+        };
+        assert!(err(&[("product", "Acme"), ("locale", "fr")]).contains("Unknown template variables: locale"));
+        assert!(err(&[]).contains("Missing values for template variables: product, tier"));
+        assert!(err(&[("product", "Acme"), ("seats", "many")]).contains("expects Integer"));
+    }
+
+    #[test]
+    fn test_variable_type_defaults_to_string() {
+        let variable: TemplateVariable =
+            serde_json::from_str(r#"{"name": "locale", "description": "UI locale"}"#).unwrap();
+        assert_eq!(variable.kind, VariableType::String);
+        assert!(variable.default.is_none());
+    }
+}
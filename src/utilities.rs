@@ -37,6 +37,22 @@ pub fn require_env(name: &str) -> Result<String> {
     }
 }
 
+/// Returns the current time in seconds since the Unix epoch.
+///
+/// # Examples
+///
+/// ```
+/// use rigscribe::utilities::unix_now;
+///
+/// assert!(unix_now() > 1_700_000_000);
+/// ```
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
 /// Saves an [`Artifact`] to disk as a JSON file.
 ///
 /// If the provided path does not have a `.json` extension, it will be appended.